@@ -0,0 +1,217 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    // What a solution gave, but never submitted, so it might not be right
+    Unconfirmed,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Answer {
+    pub day: u8,
+    pub part: u8,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+/// Everything we know about the answers to each part, from the answers file.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Answers {
+    answers: Vec<Answer>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Check {
+    Pass,
+    Fail(String),
+    // Nothing rules the answer out, but we don't know the right one either
+    Unknown,
+    // Like `Unknown`, but with an unconfirmed answer, and what it was if this one is different
+    Unconfirmed(Option<String>),
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Check::Pass => write!(f, "pass"),
+            Check::Fail(reason) => write!(f, "FAIL ({})", reason),
+            Check::Unknown => write!(f, "unknown"),
+            Check::Unconfirmed(None) => write!(f, "unconfirmed"),
+            Check::Unconfirmed(Some(was)) => write!(f, "unconfirmed, was {}", was),
+        }
+    }
+}
+
+// Compares answers as numbers where they are, since "99" < "100"
+fn compare(a: &str, b: &str) -> Option<Ordering> {
+    match (a.parse::<i128>(), b.parse::<i128>()) {
+        (Ok(a), Ok(b)) => Some(a.cmp(&b)),
+        _ => None,
+    }
+}
+
+impl Answers {
+    pub fn parse(contents: &str) -> Result<Answers, String> {
+        let mut answers = vec![];
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.starts_with('#') || line.is_empty() {
+                continue;
+            }
+            let error = |expected: &str| format!("line {}: expected {}", i + 1, expected);
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [day, part, verdict, answer] = fields[..] else {
+                return Err(error("`day part verdict answer`"));
+            };
+            let day = day.parse::<u8>().map_err(|_| error("a day"))?;
+            let part = match part {
+                "1" => 1,
+                "2" => 2,
+                _ => return Err(error("part 1 or 2")),
+            };
+            let verdict = match verdict {
+                "correct" => Verdict::Correct,
+                "wrong" => Verdict::Wrong,
+                "too-high" => Verdict::TooHigh,
+                "too-low" => Verdict::TooLow,
+                "unconfirmed" => Verdict::Unconfirmed,
+                _ => {
+                    return Err(error(
+                        "`correct`, `wrong`, `too-high`, `too-low` or `unconfirmed`",
+                    ));
+                }
+            };
+            if matches!(verdict, Verdict::TooHigh | Verdict::TooLow)
+                && answer.parse::<i128>().is_err()
+            {
+                return Err(error("a numeric answer"));
+            }
+            answers.push(Answer {
+                day,
+                part,
+                verdict,
+                answer: answer.to_string(),
+            });
+        }
+        Ok(Answers { answers })
+    }
+
+    fn for_part(&self, day: u8, part: u8) -> impl Iterator<Item = &Answer> {
+        self.answers
+            .iter()
+            .filter(move |a| a.day == day && a.part == part)
+    }
+
+    /// Checks an answer against the recorded ones for its day and part.
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Check {
+        let mut correct = None;
+        let mut unconfirmed = None;
+        for known in self.for_part(day, part) {
+            let ordering = compare(answer, &known.answer);
+            let reason = match known.verdict {
+                Verdict::Correct => {
+                    correct = Some(&known.answer);
+                    continue;
+                }
+                Verdict::Unconfirmed => {
+                    unconfirmed = Some(&known.answer);
+                    continue;
+                }
+                _ if known.answer == answer => "known to be wrong".to_string(),
+                Verdict::TooHigh if ordering == Some(Ordering::Greater) => {
+                    format!("too high, {} was already too high", known.answer)
+                }
+                Verdict::TooLow if ordering == Some(Ordering::Less) => {
+                    format!("too low, {} was already too low", known.answer)
+                }
+                _ => continue,
+            };
+            return Check::Fail(reason);
+        }
+        match correct {
+            Some(correct) if correct == answer => Check::Pass,
+            Some(correct) => Check::Fail(format!("expected {}", correct)),
+            None => match unconfirmed {
+                Some(was) if was == answer => Check::Unconfirmed(None),
+                Some(was) => Check::Unconfirmed(Some(was.clone())),
+                None => Check::Unknown,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::{Answers, Check};
+
+    const ANSWERS: &str = "# day part verdict answer
+6 1 too-low 5171058832173
+6 1 correct 5171061464548
+6 2 too-high 200
+6 2 wrong 150
+7 1 unconfirmed 1573
+7 1 too-low 1000
+";
+
+    #[test]
+    fn checks_against_correct_answer() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(answers.check(6, 1, "5171061464548"), Check::Pass);
+        assert_eq!(
+            answers.check(6, 1, "5171061464549"),
+            Check::Fail("expected 5171061464548".to_string())
+        );
+        assert_eq!(answers.check(8, 1, "42"), Check::Unknown);
+    }
+
+    #[test]
+    fn rejects_answers_outside_known_bounds() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(
+            answers.check(6, 1, "5171058832173"),
+            Check::Fail("known to be wrong".to_string())
+        );
+        assert_eq!(
+            answers.check(6, 1, "99"),
+            Check::Fail("too low, 5171058832173 was already too low".to_string())
+        );
+        assert_eq!(
+            answers.check(6, 2, "1000"),
+            Check::Fail("too high, 200 was already too high".to_string())
+        );
+        assert_eq!(
+            answers.check(6, 2, "150"),
+            Check::Fail("known to be wrong".to_string())
+        );
+        assert_eq!(answers.check(6, 2, "199"), Check::Unknown);
+    }
+
+    #[test]
+    fn unconfirmed_answers_never_pass() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(answers.check(7, 1, "1573"), Check::Unconfirmed(None));
+        assert_eq!(
+            answers.check(7, 1, "1574"),
+            Check::Unconfirmed(Some("1573".to_string()))
+        );
+        assert_eq!(
+            answers.check(7, 1, "999"),
+            Check::Fail("too low, 1000 was already too low".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_errors_name_the_line() {
+        let err = Answers::parse("1 1 correct 5\n1 3 correct 5").unwrap_err();
+        assert_eq!(err, "line 2: expected part 1 or 2");
+        let err = Answers::parse("1 1 too-high abc").unwrap_err();
+        assert_eq!(err, "line 1: expected a numeric answer");
+    }
+}
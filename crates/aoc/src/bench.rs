@@ -0,0 +1,352 @@
+use crate::TOO_SLOW;
+use common::{Solution, parse_or_exit};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    fs,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+const MIN_SAMPLES: usize = 10;
+const MAX_SAMPLES: usize = 100;
+const SAMPLE_TIME: Duration = Duration::from_millis(10);
+const TIME_BUDGET: Duration = Duration::from_secs(2);
+
+pub struct Options {
+    pub save: Option<String>,
+    pub baseline: Option<String>,
+    // Fraction of the baseline a benchmark can slow down by before it counts as a regression
+    pub threshold: f64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            save: None,
+            baseline: None,
+            threshold: 0.1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Step {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Step {
+    fn key(&self) -> &'static str {
+        match self {
+            Step::Parse => "parse",
+            Step::Part1 => "part1",
+            Step::Part2 => "part2",
+        }
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Parse => write!(f, "parse "),
+            Step::Part1 => write!(f, "part 1"),
+            Step::Part2 => write!(f, "part 2"),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Measurement {
+    pub day: u8,
+    pub step: Step,
+    pub case: String,
+    // Times per run, in nanoseconds
+    pub median: f64,
+    pub mad: f64,
+    pub samples: usize,
+}
+
+fn median(sorted: &[f64]) -> f64 {
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+// Median and median absolute deviation, which unlike the mean and standard deviation aren't
+// thrown off by the odd sample where the OS decided to do something else
+fn summarize(samples: &mut [f64]) -> (f64, f64) {
+    samples.sort_by(f64::total_cmp);
+    let mid = median(samples);
+    let mut deviations: Vec<f64> = samples.iter().map(|s| (s - mid).abs()).collect();
+    deviations.sort_by(f64::total_cmp);
+    (mid, median(&deviations))
+}
+
+fn measure(mut f: impl FnMut()) -> (f64, f64, usize) {
+    // Warm up, and batch up fast runs so each sample is long enough to time accurately
+    let mut batch: u32 = 1;
+    loop {
+        let start = Instant::now();
+        for _ in 0..batch {
+            f();
+        }
+        if start.elapsed() >= SAMPLE_TIME || batch >= 1 << 20 {
+            break;
+        }
+        batch *= 2;
+    }
+    let mut samples = vec![];
+    let bench_start = Instant::now();
+    while samples.len() < MIN_SAMPLES
+        || (samples.len() < MAX_SAMPLES && bench_start.elapsed() < TIME_BUDGET)
+    {
+        let start = Instant::now();
+        for _ in 0..batch {
+            f();
+        }
+        samples.push(start.elapsed().as_nanos() as f64 / batch as f64);
+    }
+    let (median, mad) = summarize(&mut samples);
+    (median, mad, samples.len())
+}
+
+fn measure_case<S: Solution>(
+    day: u8,
+    parts: &[u8],
+    case: &str,
+    path: &str,
+    input: &str,
+    config: &S::Config,
+) -> Vec<Measurement> {
+    let parsed = parse_or_exit::<S>(path, input, config);
+    let mut measurements = vec![];
+    let mut record = |step: Step, (median, mad, samples): (f64, f64, usize)| {
+        measurements.push(Measurement {
+            day,
+            step,
+            case: case.to_string(),
+            median,
+            mad,
+            samples,
+        })
+    };
+    record(
+        Step::Parse,
+        measure(|| {
+            black_box(S::parse(black_box(input), config).is_ok());
+        }),
+    );
+    for part in parts {
+        if case == "input" && TOO_SLOW.contains(&(day, *part)) {
+            continue;
+        }
+        match part {
+            1 => record(
+                Step::Part1,
+                measure(|| {
+                    black_box(S::part1(black_box(&parsed), config));
+                }),
+            ),
+            _ => record(
+                Step::Part2,
+                measure(|| {
+                    black_box(S::part2(black_box(&parsed), config));
+                }),
+            ),
+        }
+    }
+    measurements
+}
+
+/// Times parsing and each part on the real input and every example input for a day.
+pub fn bench<S: Solution>(day: u8, parts: &[u8], path: &str, input: &str) -> Vec<Measurement> {
+    eprintln!("benchmarking day {}", day);
+    let mut measurements =
+        measure_case::<S>(day, parts, "input", path, input, &S::Config::default());
+    for example in S::examples() {
+        let example_parts: Vec<u8> = parts
+            .iter()
+            .copied()
+            .filter(|part| example.parts.contains(part))
+            .collect();
+        let case = format!("example {}", example.name);
+        measurements.extend(measure_case::<S>(
+            day,
+            &example_parts,
+            &case,
+            &case,
+            example.input,
+            &example.config,
+        ));
+    }
+    measurements
+}
+
+fn format_nanos(nanos: f64) -> String {
+    if nanos < 1e3 {
+        format!("{:.0}ns", nanos)
+    } else if nanos < 1e6 {
+        format!("{:.2}µs", nanos / 1e3)
+    } else if nanos < 1e9 {
+        format!("{:.2}ms", nanos / 1e6)
+    } else {
+        format!("{:.2}s", nanos / 1e9)
+    }
+}
+
+type BaselineKey = (u8, String, String);
+
+fn parse_baseline(contents: &str) -> Result<HashMap<BaselineKey, f64>, String> {
+    let mut baseline = HashMap::new();
+    for (i, line) in contents.lines().enumerate() {
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split('\t').collect();
+        let parsed = match fields[..] {
+            [day, step, case, median] => day
+                .parse::<u8>()
+                .ok()
+                .zip(median.parse::<f64>().ok())
+                .map(|(day, median)| ((day, step.to_string(), case.to_string()), median)),
+            _ => None,
+        };
+        match parsed {
+            Some((key, median)) => {
+                baseline.insert(key, median);
+            }
+            None => return Err(format!("line {}: expected `day\tstep\tcase\tnanos`", i + 1)),
+        }
+    }
+    Ok(baseline)
+}
+
+fn format_baseline(measurements: &[Measurement]) -> String {
+    let mut contents = String::from("# day\tstep\tcase\tmedian nanoseconds\n");
+    for m in measurements {
+        contents.push_str(&format!(
+            "{}\t{}\t{}\t{:.0}\n",
+            m.day,
+            m.step.key(),
+            m.case,
+            m.median
+        ));
+    }
+    contents
+}
+
+// A slowdown only counts if it's past the threshold and well outside the measurement noise
+fn is_regression(m: &Measurement, baseline: f64, threshold: f64) -> bool {
+    m.median > baseline * (1.0 + threshold) && m.median - baseline > 3.0 * m.mad
+}
+
+/// Prints the measurements, compared against the baseline if there is one, and saves them if
+/// asked to. Returns false if anything regressed.
+pub fn report(measurements: &[Measurement], options: &Options) -> bool {
+    let baseline = match &options.baseline {
+        Some(path) => match fs::read_to_string(path).map_err(|e| e.to_string()) {
+            Ok(contents) => match parse_baseline(&contents) {
+                Ok(baseline) => Some(baseline),
+                Err(e) => {
+                    eprintln!("could not parse baseline {}: {}", path, e);
+                    return false;
+                }
+            },
+            Err(e) => {
+                eprintln!("could not read baseline {}: {}", path, e);
+                return false;
+            }
+        },
+        None => None,
+    };
+    let case_width = measurements.iter().map(|m| m.case.len()).max().unwrap_or(0);
+    let mut regressions = 0;
+    for m in measurements {
+        let mut line = format!(
+            "day {:>2} {} {:<width$} {:>10} ± {:<10} ({} samples)",
+            m.day,
+            m.step,
+            m.case,
+            format_nanos(m.median),
+            format_nanos(m.mad),
+            m.samples,
+            width = case_width
+        );
+        if let Some(baseline) = &baseline {
+            match baseline.get(&(m.day, m.step.key().to_string(), m.case.clone())) {
+                Some(base) => {
+                    let change = (m.median - base) / base * 100.0;
+                    line.push_str(&format!(" {:+.1}%", change));
+                    if is_regression(m, *base, options.threshold) {
+                        regressions += 1;
+                        line.push_str(" REGRESSED");
+                    }
+                }
+                None => line.push_str(" (no baseline)"),
+            }
+        }
+        println!("{}", line);
+    }
+    if let Some(path) = &options.save {
+        if let Err(e) = fs::write(path, format_baseline(measurements)) {
+            eprintln!("could not save baseline {}: {}", path, e);
+            return false;
+        }
+        eprintln!("saved baseline to {}", path);
+    }
+    if regressions > 0 {
+        eprintln!(
+            "{} benchmark{} regressed by more than {:.0}%",
+            regressions,
+            if regressions == 1 { "" } else { "s" },
+            options.threshold * 100.0
+        );
+        return false;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bench::{
+        Measurement, Step, format_baseline, is_regression, parse_baseline, summarize,
+    };
+
+    fn measurement(median: f64, mad: f64) -> Measurement {
+        Measurement {
+            day: 7,
+            step: Step::Part2,
+            case: "example basic".to_string(),
+            median,
+            mad,
+            samples: 10,
+        }
+    }
+
+    #[test]
+    fn summarize_ignores_outliers() {
+        let mut samples = vec![10.0, 11.0, 9.0, 10.0, 1000.0];
+        assert_eq!(summarize(&mut samples), (10.0, 1.0));
+    }
+
+    #[test]
+    fn baseline_round_trips() {
+        let saved = format_baseline(&[measurement(1234.0, 5.0)]);
+        let baseline = parse_baseline(&saved).unwrap();
+        let key = (7, "part2".to_string(), "example basic".to_string());
+        assert_eq!(baseline.get(&key), Some(&1234.0));
+        assert!(parse_baseline("7\tpart2\tnot enough fields").is_err());
+    }
+
+    #[test]
+    fn regressions_need_to_beat_threshold_and_noise() {
+        assert!(is_regression(&measurement(150.0, 1.0), 100.0, 0.1));
+        assert!(!is_regression(&measurement(105.0, 1.0), 100.0, 0.1));
+        assert!(!is_regression(&measurement(150.0, 20.0), 100.0, 0.1));
+    }
+}
@@ -0,0 +1,334 @@
+mod answers;
+mod bench;
+
+use answers::{Answers, Check};
+use common::{Solution, parse_or_exit};
+use std::{env, fs::read_to_string, process::exit, time::Instant};
+
+const USAGE: &str = "usage:
+    aoc run <day> [--part <1|2>] [--input <path>]
+    aoc bench [<day>] [--part <1|2>] [--save <path>] [--baseline <path>] [--threshold <percent>]
+    aoc verify [<day>] [--part <1|2>] [--answers <path>]";
+
+// Parts that take too long on the real input to run over and over
+const TOO_SLOW: &[(u8, u8)] = &[(11, 2)];
+
+// Runs `$body` with `$solution` as a type alias for the given day's solution
+macro_rules! with_day {
+    ($day:expr, $solution:ident => $body:expr) => {
+        match $day {
+            1 => {
+                type $solution = day01::Day01;
+                $body
+            }
+            2 => {
+                type $solution = day02::Day02;
+                $body
+            }
+            3 => {
+                type $solution = day03::Day03;
+                $body
+            }
+            4 => {
+                type $solution = day04::Day04;
+                $body
+            }
+            5 => {
+                type $solution = day05::Day05;
+                $body
+            }
+            6 => {
+                type $solution = day06::Day06;
+                $body
+            }
+            7 => {
+                type $solution = day07::Day07;
+                $body
+            }
+            8 => {
+                type $solution = day08::Day08;
+                $body
+            }
+            9 => {
+                type $solution = day09::Day09;
+                $body
+            }
+            10 => {
+                type $solution = day10::Day10;
+                $body
+            }
+            11 => {
+                type $solution = day11::Day11;
+                $body
+            }
+            12 => {
+                type $solution = day12::Day12;
+                $body
+            }
+            _ => unreachable!(),
+        }
+    };
+}
+
+enum Command {
+    Run {
+        day: u8,
+        parts: Vec<u8>,
+        input_path: String,
+    },
+    Bench {
+        days: Vec<u8>,
+        parts: Vec<u8>,
+        options: bench::Options,
+    },
+    Verify {
+        days: Vec<u8>,
+        parts: Vec<u8>,
+        answers_path: String,
+    },
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("{}", USAGE);
+    exit(1)
+}
+
+fn default_input_path(day: u8) -> String {
+    format!("{}/../day{:02}/input.txt", env!("CARGO_MANIFEST_DIR"), day)
+}
+
+fn default_answers_path() -> String {
+    format!("{}/answers.txt", env!("CARGO_MANIFEST_DIR"))
+}
+
+fn read_input(path: &str) -> String {
+    match read_to_string(path) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("could not read {}: {}", path, e);
+            exit(1)
+        }
+    }
+}
+
+fn parse_day(arg: &str) -> u8 {
+    match arg.parse::<u8>() {
+        Ok(day) if (1..=12).contains(&day) => day,
+        _ => usage_error("day must be a number from 1 to 12"),
+    }
+}
+
+fn flag_value<'a>(iter: &mut impl Iterator<Item = &'a String>, flag: &str) -> &'a str {
+    match iter.next() {
+        Some(value) => value,
+        None => usage_error(&format!("missing value for {}", flag)),
+    }
+}
+
+fn parse_args(args: &[String]) -> Command {
+    let mut iter = args.iter();
+    let command = match iter.next() {
+        Some(command) => command.as_str(),
+        None => usage_error("missing command"),
+    };
+    let mut day = None;
+    let mut parts = vec![1, 2];
+    let mut input_path = None;
+    let mut answers_path = None;
+    let mut options = bench::Options::default();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--part" => match flag_value(&mut iter, arg) {
+                "1" => parts = vec![1],
+                "2" => parts = vec![2],
+                _ => usage_error("part must be 1 or 2"),
+            },
+            "--input" if command == "run" => {
+                input_path = Some(flag_value(&mut iter, arg).to_string())
+            }
+            "--save" if command == "bench" => {
+                options.save = Some(flag_value(&mut iter, arg).to_string())
+            }
+            "--baseline" if command == "bench" => {
+                options.baseline = Some(flag_value(&mut iter, arg).to_string())
+            }
+            "--threshold" if command == "bench" => {
+                options.threshold = match flag_value(&mut iter, arg).parse::<f64>() {
+                    Ok(percent) if percent >= 0.0 => percent / 100.0,
+                    _ => usage_error("threshold must be a non-negative percentage"),
+                }
+            }
+            "--answers" if command == "verify" => {
+                answers_path = Some(flag_value(&mut iter, arg).to_string())
+            }
+            _ if day.is_none() && !arg.starts_with("--") => day = Some(parse_day(arg)),
+            _ => usage_error(&format!("unknown argument: {}", arg)),
+        }
+    }
+    let days = day.map_or_else(|| (1..=12).collect(), |day| vec![day]);
+    match command {
+        "run" => {
+            let day = day.unwrap_or_else(|| usage_error("missing day"));
+            Command::Run {
+                day,
+                parts,
+                input_path: input_path.unwrap_or_else(|| default_input_path(day)),
+            }
+        }
+        "bench" => Command::Bench {
+            days,
+            parts,
+            options,
+        },
+        "verify" => Command::Verify {
+            days,
+            parts,
+            answers_path: answers_path.unwrap_or_else(default_answers_path),
+        },
+        _ => usage_error(&format!("unknown command: {}", command)),
+    }
+}
+
+fn solve<S: Solution>(part: u8, parsed: &S::Input, config: &S::Config) -> String {
+    match part {
+        1 => S::part1(parsed, config).to_string(),
+        _ => S::part2(parsed, config).to_string(),
+    }
+}
+
+fn run<S: Solution>(day: u8, parts: &[u8], path: &str, input: &str) {
+    let config = S::Config::default();
+    let start = Instant::now();
+    let parsed = parse_or_exit::<S>(path, input, &config);
+    let parse_time = start.elapsed();
+    for part in parts {
+        let start = Instant::now();
+        let answer = solve::<S>(*part, &parsed, &config);
+        println!(
+            "day {} part {}: {} ({:?} + {:?} parsing)",
+            day,
+            part,
+            answer,
+            start.elapsed(),
+            parse_time
+        );
+    }
+}
+
+// Checks each part's answer against the answers file, returning false if any are wrong
+fn verify<S: Solution>(day: u8, parts: &[u8], answers: &Answers) -> bool {
+    let path = default_input_path(day);
+    let input = read_input(&path);
+    let config = S::Config::default();
+    let parsed = parse_or_exit::<S>(&path, &input, &config);
+    let mut ok = true;
+    for part in parts {
+        if TOO_SLOW.contains(&(day, *part)) {
+            println!("day {} part {}: skipped, too slow", day, part);
+            continue;
+        }
+        let answer = solve::<S>(*part, &parsed, &config);
+        let check = answers.check(day, *part, &answer);
+        ok &= !matches!(check, Check::Fail(_));
+        println!("day {} part {}: {} {}", day, part, answer, check);
+    }
+    ok
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match parse_args(&args) {
+        Command::Run {
+            day,
+            parts,
+            input_path,
+        } => {
+            let input = read_input(&input_path);
+            with_day!(day, S => run::<S>(day, &parts, &input_path, &input))
+        }
+        Command::Bench {
+            days,
+            parts,
+            options,
+        } => {
+            let mut results = vec![];
+            for day in days {
+                let input_path = default_input_path(day);
+                let input = read_input(&input_path);
+                with_day!(day, S => results.extend(bench::bench::<S>(day, &parts, &input_path, &input)))
+            }
+            if !bench::report(&results, &options) {
+                exit(1)
+            }
+        }
+        Command::Verify {
+            days,
+            parts,
+            answers_path,
+        } => {
+            let answers = match Answers::parse(&read_input(&answers_path)) {
+                Ok(answers) => answers,
+                Err(e) => {
+                    eprintln!("{}: {}", answers_path, e);
+                    exit(1)
+                }
+            };
+            let mut ok = true;
+            for day in days {
+                ok &= with_day!(day, S => verify::<S>(day, &parts, &answers));
+            }
+            if !ok {
+                exit(1)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+    use std::{env, process::Command};
+
+    const CHILD: &str = "AOC_EXAMPLES_CHILD";
+    const START: &str = "<examples>";
+    const END: &str = "</examples>";
+
+    fn run_examples<S: Solution>() {
+        for example in S::examples() {
+            let parsed = S::parse(example.input, &example.config).unwrap();
+            for part in example.parts {
+                match part {
+                    1 => S::part1(&parsed, &example.config).to_string(),
+                    _ => S::part2(&parsed, &example.config).to_string(),
+                };
+            }
+        }
+    }
+
+    // Bench and verify print to stdout, so anything a solution prints would end up mixed into
+    // their output. This runs every example in a child process, where output isn't captured,
+    // and checks nothing came out between the markers or on stderr.
+    #[test]
+    fn solutions_never_print() {
+        if env::var_os(CHILD).is_some() {
+            print!("{}", START);
+            for day in 1..=12 {
+                with_day!(day, S => run_examples::<S>())
+            }
+            print!("{}", END);
+            return;
+        }
+        let output = Command::new(env::current_exe().unwrap())
+            .args(["tests::solutions_never_print", "--exact", "--nocapture"])
+            .env(CHILD, "1")
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "{}", stdout);
+        let (_, rest) = stdout.split_once(START).unwrap();
+        let (printed, _) = rest.split_once(END).unwrap();
+        assert_eq!(printed, "");
+        assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+    }
+}
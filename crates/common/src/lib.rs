@@ -0,0 +1,64 @@
+mod parse;
+
+pub use parse::{Line, ParseError, lines};
+use std::{
+    fmt::{self, Display},
+    process::exit,
+};
+
+/// A day's puzzle, split into a parse step shared by both parts and the two parts themselves.
+///
+/// Anything a day needs besides the puzzle input (like how many connections day 8 makes) goes
+/// in `Config`, whose `Default` is what the real puzzle input uses.
+pub trait Solution {
+    type Config: Default;
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str, config: &Self::Config) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input, config: &Self::Config) -> Self::Answer1;
+    fn part2(input: &Self::Input, config: &Self::Config) -> Self::Answer2;
+
+    /// The example inputs from the puzzle text.
+    fn examples() -> Vec<Example<Self::Config>> {
+        vec![]
+    }
+
+    fn solve_part1(input: &str, config: &Self::Config) -> Result<Self::Answer1, ParseError> {
+        Ok(Self::part1(&Self::parse(input, config)?, config))
+    }
+
+    fn solve_part2(input: &str, config: &Self::Config) -> Result<Self::Answer2, ParseError> {
+        Ok(Self::part2(&Self::parse(input, config)?, config))
+    }
+}
+
+/// An example input from the puzzle text, along with the config and parts it works for.
+pub struct Example<C> {
+    pub name: &'static str,
+    pub input: &'static str,
+    pub config: C,
+    pub parts: &'static [u8],
+}
+
+/// Parses `input`, read from `path`, or prints where it went wrong and exits.
+pub fn parse_or_exit<S: Solution>(path: &str, input: &str, config: &S::Config) -> S::Input {
+    match S::parse(input, config) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            exit(1)
+        }
+    }
+}
+
+/// Answer for a part that doesn't have a puzzle, like day 12 part 2.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Unsolved;
+
+impl Display for Unsolved {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not implemented")
+    }
+}
@@ -0,0 +1,140 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// Where and why a puzzle input couldn't be parsed. Lines and columns count from 1.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+    pub source_line: String,
+}
+
+impl ParseError {
+    /// An error for input that stopped before something it needed, like a missing section.
+    pub fn end_of_input(input: &str, expected: impl Into<String>) -> Self {
+        ParseError {
+            line: input.lines().count() + 1,
+            column: 1,
+            expected: expected.into(),
+            found: "end of input".to_string(),
+            source_line: String::new(),
+        }
+    }
+
+    /// Moves the error down by `lines`, for errors from parsing a piece of the input on its own.
+    pub fn below(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )?;
+        if !self.source_line.is_empty() {
+            write!(
+                f,
+                "\n    {}\n    {}^",
+                self.source_line,
+                " ".repeat(self.column - 1)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+/// A line of puzzle input along with its line number, so errors can point into it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// An error pointing at `at`, which must be a slice of this line's text. An empty slice at
+    /// the end of the line reports that the line ended early.
+    pub fn error(&self, at: &str, expected: impl Into<String>) -> ParseError {
+        let offset = (at.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+        debug_assert!(offset <= self.text.len());
+        let offset = offset.min(self.text.len());
+        let found = if at.is_empty() {
+            "end of line".to_string()
+        } else {
+            format!("`{}`", at)
+        };
+        ParseError {
+            line: self.number,
+            column: self.text[..offset].chars().count() + 1,
+            expected: expected.into(),
+            found,
+            source_line: self.text.to_string(),
+        }
+    }
+
+    /// An error pointing just past the end of the line.
+    pub fn end_error(&self, expected: impl Into<String>) -> ParseError {
+        self.error(&self.text[self.text.len()..], expected)
+    }
+
+    /// Parses `part`, a slice of this line's text.
+    pub fn parse<T: FromStr>(&self, part: &str, expected: &str) -> Result<T, ParseError> {
+        part.parse::<T>().map_err(|_| self.error(part, expected))
+    }
+
+    /// Splits the line in two around `delimiter`.
+    pub fn split_once(
+        &self,
+        part: &'a str,
+        delimiter: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        part.split_once(delimiter)
+            .ok_or_else(|| self.error(&part[part.len()..], format!("`{}`", delimiter)))
+    }
+}
+
+/// The lines of `input`, numbered from 1.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        number: i + 1,
+        text,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParseError, lines};
+
+    #[test]
+    fn errors_point_at_the_slice() {
+        let line = lines("ab\n12,x4").nth(1).unwrap();
+        let (_, y) = line.split_once(line.text, ",").unwrap();
+        let err = line.parse::<u8>(y, "a number").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 4: expected a number, found `x4`\n    12,x4\n       ^"
+        );
+        let err = line.split_once(y, ",").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 6));
+        assert_eq!(err.found, "end of line");
+    }
+
+    #[test]
+    fn end_of_input_is_past_the_last_line() {
+        let err = ParseError::end_of_input("a\nb\n", "more");
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 1: expected more, found end of input"
+        );
+    }
+}
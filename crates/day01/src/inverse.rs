@@ -0,0 +1,103 @@
+/// The shortest list of rotations that takes a dial of `size` positions, starting at `start`,
+/// to exactly `landings` stops on zero and `passes` clicks onto zero. Each landing needs its
+/// own rotation, but one rotation can pass zero any number of times, so this is as many
+/// rotations as landings, or one to pass zero without ever stopping there. Returns `None` if
+/// no list of rotations can do it, like landing on zero without passing it from a nonzero start.
+pub fn rotations_for(size: i64, start: i64, landings: u64, passes: u64) -> Option<Vec<i64>> {
+    assert!(size > 0 && (0..size).contains(&start));
+    let clicks = |turns: u64| size.checked_mul(i64::try_from(turns).ok()?);
+    if landings == 0 {
+        if passes == 0 {
+            return Some(vec![]);
+        }
+        // Every position of a one-position dial is zero, so it can't turn without landing
+        if size == 1 {
+            return None;
+        }
+        // Go round past zero and stop one click after it
+        let to_zero = if start == 0 { size } else { size - start };
+        return clicks(passes - 1)?
+            .checked_add(to_zero + 1)
+            .map(|r| vec![r]);
+    }
+    // Getting to zero from anywhere else passes it once
+    let first_passes = (start != 0) as u64;
+    if passes < first_passes {
+        return None;
+    }
+    // Each later rotation goes once round while there are passes to spare, or stays put
+    let later = landings - 1;
+    let full_turns = later.min(passes - first_passes);
+    let extra = passes - first_passes - full_turns;
+    let first = if start == 0 {
+        clicks(extra)?
+    } else {
+        -(clicks(extra)?.checked_add(start)?)
+    };
+    let mut rotations = vec![first];
+    rotations.extend((0..full_turns).map(|_| size));
+    rotations.extend((full_turns..later).map(|_| 0));
+    Some(rotations)
+}
+
+/// Rotations in the puzzle's input format, one per line.
+pub fn format_rotations(rotations: &[i64]) -> String {
+    rotations
+        .iter()
+        .map(|r| {
+            if *r < 0 {
+                format!("L{}\n", r.unsigned_abs())
+            } else {
+                format!("R{}\n", r)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Dial, format_rotations, parse_input, part1, part2, rotations_for};
+
+    #[test]
+    fn safe_dial_inputs_have_known_answers() {
+        for landings in 0..6 {
+            for passes in 0..10 {
+                let Some(rotations) = rotations_for(100, 50, landings, passes) else {
+                    assert!(landings > 0 && passes == 0);
+                    continue;
+                };
+                assert_eq!(rotations.len() as u64, landings.max((passes > 0) as u64));
+                let input = format_rotations(&rotations);
+                assert_eq!(parse_input(&input).unwrap(), rotations);
+                assert_eq!((part1(&input), part2(&input)), (landings, passes));
+            }
+        }
+    }
+
+    #[test]
+    fn other_dials() {
+        for size in 1..8 {
+            for start in 0..size {
+                for landings in 0..4 {
+                    for passes in 0..6 {
+                        let Some(rotations) = rotations_for(size, start, landings, passes) else {
+                            continue;
+                        };
+                        let mut dial = Dial::new(size, start, &[0]);
+                        rotations.iter().for_each(|r| dial.rotate(*r));
+                        let counts = dial.counts(0).unwrap();
+                        assert_eq!((counts.landings, counts.passes), (landings, passes));
+                    }
+                }
+            }
+        }
+        assert_eq!(rotations_for(1, 0, 0, 1), None);
+        assert_eq!(rotations_for(10, 3, 2, 0), None);
+        assert_eq!(rotations_for(10, 0, 2, 0), Some(vec![0, 0]));
+    }
+
+    #[test]
+    fn format_matches_puzzle() {
+        assert_eq!(format_rotations(&[-68, 48, 0]), "L68\nR48\nR0\n");
+    }
+}
@@ -0,0 +1,321 @@
+use common::{Example, Line, ParseError, Solution, lines};
+
+mod inverse;
+mod lock;
+#[cfg(test)]
+mod oracle;
+mod stream;
+mod trace;
+
+pub use inverse::{format_rotations, rotations_for};
+pub use lock::{DialReport, GearOverflow, Lock, LockInput, LockReport, parse_lock, simulate};
+pub use stream::{StreamError, Totals, stream};
+pub use trace::{TraceStep, trace, trace_csv, trace_json};
+
+// Parses `text`, a slice of `line` like `L68`, into a rotation
+fn parse_rotation(line: &Line, text: &str) -> Result<i64, ParseError> {
+    let (direction, value) = text.split_at(text.chars().next().map_or(0, char::len_utf8));
+    // Only digits, since `i64` parsing would take a sign and `L-5` would turn right
+    if !value.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(line.error(value, "a number of clicks"));
+    }
+    let value = line.parse::<i64>(value, "a number of clicks")?;
+    match direction {
+        "L" => Ok(-value),
+        "R" => Ok(value),
+        _ => Err(line.error(direction, "`L` or `R`")),
+    }
+}
+
+fn parse_line(line: Line) -> Result<i64, ParseError> {
+    parse_rotation(&line, line.text)
+}
+
+fn parse_input(input: &str) -> Result<Vec<i64>, ParseError> {
+    lines(input).map(parse_line).collect()
+}
+
+// In i128, since a rotation can be anything up to i64::MAX clicks either way
+fn next_position(cur: i64, rotation: i64, size: i64) -> i64 {
+    (cur as i128 + rotation as i128).rem_euclid(size as i128) as i64
+}
+
+// How many times a rotation points the dial at `target`, counting where it stops but not where
+// it starts
+fn passes_count(cur: i64, rotation: i64, target: i64, size: i64) -> u64 {
+    let clicks_to_target = if rotation < 0 {
+        (cur - target).rem_euclid(size)
+    } else {
+        (target - cur).rem_euclid(size)
+    };
+    let clicks_to_target = if clicks_to_target == 0 {
+        size
+    } else {
+        clicks_to_target
+    };
+    let clicks = rotation.unsigned_abs();
+    if clicks < clicks_to_target as u64 {
+        0
+    } else {
+        (clicks - clicks_to_target as u64) / size as u64 + 1
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct TargetCounts {
+    /// Rotations that stopped on the target.
+    pub landings: u64,
+    /// Clicks that pointed at the target, including ones a rotation stopped on.
+    pub passes: u64,
+}
+
+/// A dial numbered `0..size`, counting how often rotations land on or pass each target.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Dial {
+    size: i64,
+    position: i64,
+    targets: Vec<(i64, TargetCounts)>,
+}
+
+impl Dial {
+    pub fn new(size: i64, start: i64, targets: &[i64]) -> Self {
+        assert!(size > 0, "a dial needs at least one position");
+        assert!(
+            (0..size).contains(&start),
+            "start {} is off the dial",
+            start
+        );
+        for target in targets {
+            assert!(
+                (0..size).contains(target),
+                "target {} is off the dial",
+                target
+            );
+        }
+        Dial {
+            size,
+            position: start,
+            targets: targets
+                .iter()
+                .map(|t| (*t, TargetCounts::default()))
+                .collect(),
+        }
+    }
+
+    /// Turns the dial, right for positive rotations and left for negative ones. Panics if a
+    /// count overflows, which takes rotations near `i64::MAX` on a small dial.
+    pub fn rotate(&mut self, rotation: i64) {
+        self.checked_rotate(rotation)
+            .expect("dial counts overflowed");
+    }
+
+    /// Turns the dial, or leaves it where it is and returns `None` if a count would overflow.
+    pub fn checked_rotate(&mut self, rotation: i64) -> Option<()> {
+        let (position, size) = (self.position, self.size);
+        let next = next_position(position, rotation, size);
+        let counted = |target: i64, counts: &TargetCounts| {
+            let passes = passes_count(position, rotation, target, size);
+            Some(TargetCounts {
+                landings: counts.landings.checked_add((next == target) as u64)?,
+                passes: counts.passes.checked_add(passes)?,
+            })
+        };
+        if !self.targets.iter().all(|(t, c)| counted(*t, c).is_some()) {
+            return None;
+        }
+        for (target, counts) in self.targets.iter_mut() {
+            *counts = counted(*target, counts).unwrap();
+        }
+        self.position = next;
+        Some(())
+    }
+
+    pub fn size(&self) -> i64 {
+        self.size
+    }
+
+    pub fn position(&self) -> i64 {
+        self.position
+    }
+
+    /// The counts for `target`, or `None` if it isn't one of the dial's targets.
+    pub fn counts(&self, target: i64) -> Option<TargetCounts> {
+        self.targets
+            .iter()
+            .find(|(t, _)| *t == target)
+            .map(|(_, counts)| *counts)
+    }
+}
+
+fn safe_dial(rotations: &[i64]) -> Dial {
+    let mut dial = Dial::new(100, 50, &[0]);
+    for rotation in rotations.iter().copied() {
+        dial.rotate(rotation);
+    }
+    dial
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Config = ();
+    type Input = Vec<i64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str, _config: &()) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(rotations: &Self::Input, _config: &()) -> u64 {
+        safe_dial(rotations).counts(0).unwrap().landings
+    }
+
+    fn part2(rotations: &Self::Input, _config: &()) -> u64 {
+        safe_dial(rotations).counts(0).unwrap().passes
+    }
+
+    fn examples() -> Vec<Example<Self::Config>> {
+        vec![Example {
+            name: "basic",
+            input: BASIC_INPUT,
+            config: (),
+            parts: &[1, 2],
+        }]
+    }
+}
+
+pub fn part1(input: &str) -> u64 {
+    Day01::solve_part1(input, &()).unwrap()
+}
+
+pub fn part2(input: &str) -> u64 {
+    Day01::solve_part2(input, &()).unwrap()
+}
+
+pub const BASIC_INPUT: &str = "L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82";
+
+#[cfg(test)]
+mod tests {
+    use crate::{BASIC_INPUT, Dial, TargetCounts, parse_input, part1, part2, stream, trace};
+
+    #[test]
+    fn basic_test_part1() {
+        let final_position = part1(BASIC_INPUT);
+        assert_eq!(final_position, 3);
+    }
+
+    #[test]
+    fn bignum_test_part1() {
+        let basic_input = "L68
+L30
+R48
+L5
+R60
+L755
+L101
+L199
+R814
+L882";
+        let final_position = part1(basic_input);
+        assert_eq!(final_position, 3);
+    }
+
+    #[test]
+    fn basic_test_part2() {
+        let count = part2(BASIC_INPUT);
+        assert_eq!(count, 6);
+    }
+
+    #[test]
+    fn bignum_test_part2() {
+        let basic_input = "L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R214
+L882";
+        let count = part2(basic_input);
+        assert_eq!(count, 16);
+    }
+
+    #[test]
+    fn longest_rotations() {
+        assert_eq!(
+            (part1("R9223372036854775807"), part2("R9223372036854775807")),
+            (0, 92233720368547758)
+        );
+        assert_eq!(
+            (part1("L9223372036854775807"), part2("L9223372036854775807")),
+            (0, 92233720368547758)
+        );
+        let ends: Vec<i64> = trace(&[i64::MAX, -i64::MAX]).map(|step| step.end).collect();
+        assert_eq!(ends, vec![57, 50]);
+        let totals = stream(
+            "R9223372036854775807\nL9223372036854775807".as_bytes(),
+            None,
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(
+            (totals.position, totals.passes),
+            (50, 2 * 92233720368547758)
+        );
+        let mut dial = Dial::new(i64::MAX, i64::MAX - 1, &[0]);
+        dial.rotate(i64::MAX);
+        assert_eq!(dial.position(), i64::MAX - 1);
+    }
+
+    #[test]
+    fn dial_counts_each_target() {
+        let mut dial = Dial::new(60, 0, &[0, 30, 59]);
+        dial.rotate(-1);
+        dial.rotate(150);
+        assert_eq!(dial.position(), 29);
+        assert_eq!(
+            dial.counts(59),
+            Some(TargetCounts {
+                landings: 1,
+                passes: 3
+            })
+        );
+        assert_eq!(
+            dial.counts(0),
+            Some(TargetCounts {
+                landings: 0,
+                passes: 3
+            })
+        );
+        assert_eq!(dial.counts(30).unwrap().passes, 2);
+        assert_eq!(dial.counts(1), None);
+    }
+
+    #[test]
+    fn parse_errors_point_at_bad_token() {
+        let err = parse_input("L68\nX30").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.found, "`X`");
+        let err = parse_input("L68\nR").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.found, "end of line");
+        for signed in ["L-5", "R+5", "L-9223372036854775808"] {
+            let err = parse_input(signed).unwrap_err();
+            assert_eq!((err.line, err.column), (1, 2));
+            assert_eq!(err.expected, "a number of clicks");
+        }
+        assert!(parse_input("R9223372036854775808").is_err());
+    }
+}
@@ -0,0 +1,323 @@
+use crate::{Dial, parse_rotation};
+use common::{Line, ParseError, lines};
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+// Turning `from` by n clicks turns `to` by n * ratio clicks, backwards if the ratio is negative
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Gear {
+    from: usize,
+    to: usize,
+    ratio: i64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Instruction {
+    line: usize,
+    dial: usize,
+    rotation: i64,
+}
+
+/// Several named dials, some of which may be geared to turn others.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Lock {
+    names: Vec<String>,
+    dials: Vec<Dial>,
+    gears: Vec<Gear>,
+}
+
+/// A lock file: the lock as declared, and the instructions to run on it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LockInput {
+    lock: Lock,
+    instructions: Vec<Instruction>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DialReport {
+    pub name: String,
+    pub position: i64,
+    pub zero_passes: u64,
+}
+
+/// Where every dial ended up, in the order they were declared, and how often each passed zero.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LockReport {
+    pub dials: Vec<DialReport>,
+}
+
+impl Display for LockReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let combination: Vec<String> = self.dials.iter().map(|d| d.position.to_string()).collect();
+        writeln!(f, "combination: {}", combination.join("-"))?;
+        for dial in self.dials.iter() {
+            writeln!(f, "{}: passed zero {} times", dial.name, dial.zero_passes)?;
+        }
+        Ok(())
+    }
+}
+
+/// A rotation that turns a dial, itself or through gears, further than its counts can hold.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GearOverflow {
+    pub line: usize,
+    pub dial: String,
+}
+
+impl Display for GearOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: turns dial {} too far to count",
+            self.line, self.dial
+        )
+    }
+}
+
+impl Error for GearOverflow {}
+
+impl Lock {
+    fn dial(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    fn add_dial(&mut self, name: &str, size: i64, start: i64) {
+        self.names.push(name.to_string());
+        self.dials.push(Dial::new(size, start, &[0]));
+    }
+
+    fn drives(&self, from: usize, to: usize) -> bool {
+        from == to
+            || self
+                .gears
+                .iter()
+                .any(|g| g.from == from && self.drives(g.to, to))
+    }
+
+    // Turns a dial and any dials geared to it, stopping at the first one that would overflow
+    fn turn(&mut self, dial: usize, rotation: i64) -> Result<(), usize> {
+        self.dials[dial].checked_rotate(rotation).ok_or(dial)?;
+        for i in 0..self.gears.len() {
+            let gear = self.gears[i];
+            if gear.from == dial {
+                let rotation = rotation.checked_mul(gear.ratio).ok_or(gear.to)?;
+                self.turn(gear.to, rotation)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Leaves the lock partly turned if a dial overflows
+    fn rotate(&mut self, instruction: &Instruction) -> Result<(), GearOverflow> {
+        self.turn(instruction.dial, instruction.rotation)
+            .map_err(|dial| GearOverflow {
+                line: instruction.line,
+                dial: self.names[dial].clone(),
+            })
+    }
+
+    pub fn report(&self) -> LockReport {
+        LockReport {
+            dials: self
+                .names
+                .iter()
+                .zip(self.dials.iter())
+                .map(|(name, dial)| DialReport {
+                    name: name.clone(),
+                    position: dial.position(),
+                    zero_passes: dial.counts(0).unwrap().passes,
+                })
+                .collect(),
+        }
+    }
+}
+
+fn parse_name(lock: &Lock, line: &Line, name: &str) -> Result<usize, ParseError> {
+    lock.dial(name)
+        .ok_or_else(|| line.error(name, "the name of a declared dial"))
+}
+
+fn parse_declaration(lock: &mut Lock, line: &Line, words: &[&str]) -> Result<(), ParseError> {
+    let [name, size, start] = words else {
+        return Err(line.end_error("`dial <name> <size> <start>`"));
+    };
+    if lock.dial(name).is_some() || *name == "dial" || *name == "gear" {
+        return Err(line.error(name, "a new dial name"));
+    }
+    let size = line.parse::<i64>(size, "a dial size")?;
+    if size <= 0 {
+        return Err(line.error(words[1], "a positive dial size"));
+    }
+    let start = line.parse::<i64>(start, "a start position")?;
+    if !(0..size).contains(&start) {
+        return Err(line.error(words[2], format!("a start position below {}", size)));
+    }
+    lock.add_dial(name, size, start);
+    Ok(())
+}
+
+fn parse_gear(lock: &mut Lock, line: &Line, words: &[&str]) -> Result<(), ParseError> {
+    let [from, to, ratio] = words else {
+        return Err(line.end_error("`gear <from> <to> <ratio>`"));
+    };
+    let from_dial = parse_name(lock, line, from)?;
+    let to_dial = parse_name(lock, line, to)?;
+    let ratio = line.parse::<i64>(ratio, "a gear ratio")?;
+    if lock.drives(to_dial, from_dial) {
+        return Err(line.error(to, "a dial that doesn't already turn the first"));
+    }
+    lock.gears.push(Gear {
+        from: from_dial,
+        to: to_dial,
+        ratio,
+    });
+    Ok(())
+}
+
+/// Parses a lock file. Each line is one of
+///
+/// - `dial <name> <size> <start>`, declaring a dial
+/// - `gear <from> <to> <ratio>`, so turning `from` turns `to` by `ratio` times as much
+/// - `<name> L68` or `<name> R48`, turning a dial
+/// - `L68` or `R48`, turning the first dial
+///
+/// With no dials declared, the first instruction declares a 100-position dial called `safe`
+/// starting at 50, so plain puzzle inputs are lock files too. Blank lines and lines starting
+/// with `#` are ignored.
+pub fn parse_lock(input: &str) -> Result<LockInput, ParseError> {
+    let mut lock = Lock {
+        names: vec![],
+        dials: vec![],
+        gears: vec![],
+    };
+    let mut instructions = vec![];
+    for line in lines(input) {
+        let words: Vec<&str> = line.text.split_whitespace().collect();
+        match words[..] {
+            [] => {}
+            [comment, ..] if comment.starts_with('#') => {}
+            ["dial", ref rest @ ..] => parse_declaration(&mut lock, &line, rest)?,
+            ["gear", ref rest @ ..] => parse_gear(&mut lock, &line, rest)?,
+            [rotation] => {
+                let rotation = parse_rotation(&line, rotation)?;
+                if lock.dials.is_empty() {
+                    lock.add_dial("safe", 100, 50);
+                }
+                instructions.push(Instruction {
+                    line: line.number,
+                    dial: 0,
+                    rotation,
+                });
+            }
+            [name, rotation] => {
+                let dial = parse_name(&lock, &line, name)?;
+                let rotation = parse_rotation(&line, rotation)?;
+                instructions.push(Instruction {
+                    line: line.number,
+                    dial,
+                    rotation,
+                });
+            }
+            _ => return Err(line.error(words[2], "end of line")),
+        }
+    }
+    Ok(LockInput { lock, instructions })
+}
+
+/// Runs the instructions on the lock.
+pub fn simulate(input: &LockInput) -> Result<LockReport, GearOverflow> {
+    let mut lock = input.lock.clone();
+    for instruction in input.instructions.iter() {
+        lock.rotate(instruction)?;
+    }
+    Ok(lock.report())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BASIC_INPUT, DialReport, GearOverflow, parse_lock, simulate};
+
+    const GEARED_INPUT: &str = "# the inner dial turns backwards at twice the speed
+dial outer 100 50
+dial inner 60 0
+dial pin 10 5
+gear outer inner -2
+outer L68
+inner R15
+pin L25
+outer R2";
+
+    #[test]
+    fn plain_input_is_one_dial() {
+        let report = simulate(&parse_lock(BASIC_INPUT).unwrap()).unwrap();
+        assert_eq!(
+            report.dials,
+            vec![DialReport {
+                name: "safe".to_string(),
+                position: 32,
+                zero_passes: 6
+            }]
+        );
+    }
+
+    #[test]
+    fn gears_turn_other_dials() {
+        let report = simulate(&parse_lock(GEARED_INPUT).unwrap()).unwrap();
+        let positions: Vec<i64> = report.dials.iter().map(|d| d.position).collect();
+        // inner turns R136, R15 then L4
+        assert_eq!(positions, vec![84, 27, 0]);
+        let passes: Vec<u64> = report.dials.iter().map(|d| d.zero_passes).collect();
+        assert_eq!(passes, vec![1, 2, 3]);
+        assert_eq!(
+            report.to_string(),
+            "combination: 84-27-0
+outer: passed zero 1 times
+inner: passed zero 2 times
+pin: passed zero 3 times
+"
+        );
+    }
+
+    #[test]
+    fn geared_overflow_is_an_error() {
+        let input = "dial a 10 0\ndial b 10 0\ndial c 10 0\ngear a b 1000000000000\ngear b c 1000000000000\na R5\na R5000000";
+        let err = simulate(&parse_lock(input).unwrap()).unwrap_err();
+        assert_eq!(
+            err,
+            GearOverflow {
+                line: 6,
+                dial: "c".to_string()
+            }
+        );
+        let err = simulate(&parse_lock(&input.replace("a R5\na R5000000", "a R50000000")).unwrap())
+            .unwrap_err();
+        assert_eq!(err.line, 6);
+        assert_eq!(err.dial, "b");
+        let report = simulate(&parse_lock("dial a 10 9\na R9223372036854775807").unwrap()).unwrap();
+        assert_eq!(report.dials[0].position, 6);
+        assert_eq!(report.dials[0].zero_passes, 922337203685477581);
+        let err = simulate(&parse_lock("dial a 1 0\na R9223372036854775807\na L9223372036854775807\na R9223372036854775807").unwrap()).unwrap_err();
+        assert_eq!(
+            err,
+            GearOverflow {
+                line: 4,
+                dial: "a".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn lock_errors_point_at_bad_token() {
+        let err = parse_lock("dial a 10 0\nb R5").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "the name of a declared dial");
+        let err = parse_lock("dial a 10 0\na X5").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        let err = parse_lock("dial a 10 0\ndial b 10 0\ngear a b 1\ngear b a 1").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 8));
+        let err = parse_lock("dial a 10 10").unwrap_err();
+        assert_eq!(err.expected, "a start position below 10");
+    }
+}
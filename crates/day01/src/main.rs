@@ -1,125 +1,123 @@
-use std::fs::read_to_string;
+use common::{Solution, parse_or_exit};
+use day01::{
+    Day01, StreamError, Totals, format_rotations, parse_lock, rotations_for, simulate, stream,
+    trace, trace_csv, trace_json,
+};
+use std::{
+    env,
+    fs::{File, read_to_string},
+    io::{self, BufReader},
+    process::exit,
+};
 
-fn parse_line(line: &str) -> i64 {
-    let value = line[1..].parse::<i64>().unwrap();
-    if line.starts_with('L') { -value } else { value }
-}
-
-fn parse_input(input: &str) -> Vec<i64> {
-    input.lines().map(parse_line).collect()
-}
+const STREAM_USAGE: &str = "usage: day01 --stream [<path>|-] [--every <rotations>]";
 
-fn next_position(cur: i64, rotation: i64) -> i64 {
-    let next = (cur + rotation) % 100;
-    if next < 0 { next + 100 } else { next }
-}
-
-fn passes_zero_count(cur: i64, rotation: i64) -> i64 {
-    let passes = ((cur + rotation) / 100).abs();
-    if cur + rotation <= 0 && cur != 0 {
-        passes + 1
-    } else {
-        passes
-    }
+fn print_totals(totals: &Totals) {
+    println!(
+        "after {} rotations: part 1 {}, part 2 {}",
+        totals.rotations, totals.landings, totals.passes
+    );
 }
 
-fn part1(input: &str) -> i64 {
-    let rotations = parse_input(input);
-    let mut position = 50;
-    let mut answer = 0;
-    for rotation in rotations {
-        position = next_position(position, rotation);
-        if position == 0 {
-            answer += 1;
+// Streams rotations from a file, or stdin for `-`, printing running totals along the way
+fn run_stream(args: &[String]) {
+    let mut path = "-";
+    let mut every = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--every" => match iter.next().and_then(|n| n.parse::<u64>().ok()) {
+                Some(n) if n > 0 => every = Some(n),
+                _ => {
+                    eprintln!("{}", STREAM_USAGE);
+                    exit(1)
+                }
+            },
+            _ => path = arg,
         }
     }
-    answer
-}
-
-fn part2(input: &str) -> i64 {
-    let rotations = parse_input(input);
-    let mut position = 50;
-    let mut answer = 0;
-    for rotation in rotations {
-        answer += passes_zero_count(position, rotation);
-        position = next_position(position, rotation);
+    let result = if path == "-" {
+        stream(io::stdin().lock(), every, print_totals)
+    } else {
+        match File::open(path) {
+            Ok(file) => stream(BufReader::new(file), every, print_totals),
+            Err(e) => Err(StreamError::Io(e)),
+        }
+    };
+    match result {
+        Ok(totals) => {
+            println!("day 1 part 1: {}", totals.landings);
+            println!("day 1 part 2: {}", totals.passes);
+        }
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            exit(1)
+        }
     }
-    answer
 }
 
 fn main() {
-    let input = read_to_string("input.txt").unwrap();
-    let p1_answer = part1(&input);
-    println!("day 1 part 1: {}", p1_answer);
-    let p2_answer = part2(&input);
-    println!("day 1 part 2: {}", p2_answer);
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::{part1, part2};
-
-    #[test]
-    fn basic_test_part1() {
-        let basic_input = "L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R14
-L82";
-        let final_position = part1(basic_input);
-        assert_eq!(final_position, 3);
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "--stream") {
+        run_stream(&args[1..]);
+        return;
     }
-
-    #[test]
-    fn bignum_test_part1() {
-        let basic_input = "L68
-L30
-R48
-L5
-R60
-L755
-L101
-L199
-R814
-L882";
-        let final_position = part1(basic_input);
-        assert_eq!(final_position, 3);
+    // `--trace csv` or `--trace json` prints every rotation instead of the answers
+    if args.first().is_some_and(|arg| arg == "--trace") {
+        let input = read_to_string("input.txt").unwrap();
+        let rotations = parse_or_exit::<Day01>("input.txt", &input, &());
+        match args.get(1).map(String::as_str) {
+            Some("csv") => print!("{}", trace_csv(trace(&rotations))),
+            Some("json") => print!("{}", trace_json(trace(&rotations))),
+            _ => {
+                eprintln!("usage: day01 --trace <csv|json>");
+                exit(1)
+            }
+        }
+        return;
     }
-
-    #[test]
-    fn basic_test_part2() {
-        let basic_input = "L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R14
-L82";
-        let count = part2(basic_input);
-        assert_eq!(count, 6);
+    // `--generate <landings> <passes>` prints the shortest input with those answers
+    if args.first().is_some_and(|arg| arg == "--generate") {
+        let counts: Option<Vec<u64>> = args[1..].iter().map(|arg| arg.parse().ok()).collect();
+        let Some(&[landings, passes]) = counts.as_deref() else {
+            eprintln!("usage: day01 --generate <landings> <passes>");
+            exit(1)
+        };
+        match rotations_for(100, 50, landings, passes) {
+            Some(rotations) => print!("{}", format_rotations(&rotations)),
+            None => {
+                eprintln!(
+                    "no input lands on zero {} times with {} passes",
+                    landings, passes
+                );
+                exit(1)
+            }
+        }
+        return;
     }
-
-    #[test]
-    fn bignum_test_part2() {
-        let basic_input = "L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R214
-L882";
-        let count = part2(basic_input);
-        assert_eq!(count, 16);
+    // Given a lock file, simulate that instead of solving the puzzle
+    if let Some(path) = args.first() {
+        let input = read_to_string(path).unwrap();
+        let lock = match parse_lock(&input) {
+            Ok(lock) => lock,
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                exit(1)
+            }
+        };
+        match simulate(&lock) {
+            Ok(report) => print!("{}", report),
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                exit(1)
+            }
+        }
+        return;
     }
+    let input = read_to_string("input.txt").unwrap();
+    let rotations = parse_or_exit::<Day01>("input.txt", &input, &());
+    let p1_answer = Day01::part1(&rotations, &());
+    println!("day 1 part 1: {}", p1_answer);
+    let p2_answer = Day01::part2(&rotations, &());
+    println!("day 1 part 2: {}", p2_answer);
 }
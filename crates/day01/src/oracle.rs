@@ -0,0 +1,136 @@
+// A dial that turns one click at a time, to check `Dial` against
+use crate::{Dial, TargetCounts};
+
+struct ClickingDial {
+    size: i64,
+    position: i64,
+    targets: Vec<(i64, TargetCounts)>,
+}
+
+impl ClickingDial {
+    fn rotate(&mut self, rotation: i64) {
+        for _ in 0..rotation.unsigned_abs() {
+            self.position = (self.position + rotation.signum()).rem_euclid(self.size);
+            for (target, counts) in self.targets.iter_mut() {
+                if self.position == *target {
+                    counts.passes += 1;
+                }
+            }
+        }
+        for (target, counts) in self.targets.iter_mut() {
+            if self.position == *target {
+                counts.landings += 1;
+            }
+        }
+    }
+}
+
+// xorshift64*, so the tests need no dependencies and every run checks the same cases
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn rotation(&mut self, size: i64) -> i64 {
+        // Mostly short turns, but some many times round, and plenty of stops on the targets
+        let clicks = match self.below(4) {
+            0 => self.below(size as u64 * 50) as i64,
+            1 => size * self.below(20) as i64,
+            _ => self.below(size as u64 * 2) as i64,
+        };
+        if self.below(2) == 0 { -clicks } else { clicks }
+    }
+}
+
+fn check(rng: &mut Rng, size: i64, rotations: usize) {
+    let start = rng.below(size as u64) as i64;
+    let mut targets = vec![0, size - 1, rng.below(size as u64) as i64];
+    targets.sort();
+    targets.dedup();
+    let mut dial = Dial::new(size, start, &targets);
+    let mut oracle = ClickingDial {
+        size,
+        position: start,
+        targets: targets
+            .iter()
+            .map(|t| (*t, TargetCounts::default()))
+            .collect(),
+    };
+    for _ in 0..rotations {
+        let rotation = rng.rotation(size);
+        dial.rotate(rotation);
+        oracle.rotate(rotation);
+        assert_eq!(
+            dial.position(),
+            oracle.position,
+            "size {} start {}",
+            size,
+            start
+        );
+        for (target, counts) in oracle.targets.iter() {
+            assert_eq!(
+                dial.counts(*target),
+                Some(*counts),
+                "size {} start {} target {}",
+                size,
+                start,
+                target
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Dial, TargetCounts,
+        oracle::{ClickingDial, Rng, check},
+    };
+
+    #[test]
+    fn matches_oracle_on_safe_dial() {
+        let mut rng = Rng(0x5afe);
+        for _ in 0..200 {
+            check(&mut rng, 100, 50);
+        }
+    }
+
+    #[test]
+    fn matches_oracle_on_any_size() {
+        let mut rng = Rng(0xd1a1);
+        for size in (1..=12).chain([60, 97, 360]) {
+            for _ in 0..50 {
+                check(&mut rng, size, 50);
+            }
+        }
+    }
+
+    #[test]
+    fn matches_oracle_on_huge_rotations() {
+        let mut rng = Rng(0xb16);
+        for size in [1, 2, 7, 100] {
+            let mut dial = Dial::new(size, 0, &[0]);
+            let mut oracle = ClickingDial {
+                size,
+                position: 0,
+                targets: vec![(0, TargetCounts::default())],
+            };
+            for _ in 0..20 {
+                let rotation = rng.rotation(size) * 1000 + rng.rotation(size);
+                dial.rotate(rotation);
+                oracle.rotate(rotation);
+                assert_eq!(dial.position(), oracle.position);
+                assert_eq!(dial.counts(0), Some(oracle.targets[0].1));
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{Dial, parse_line};
+use common::{Line, ParseError};
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    io::{self, BufRead},
+};
+
+/// Running totals for the safe dial, as of some number of rotations.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Totals {
+    pub rotations: u64,
+    pub position: i64,
+    /// The part 1 answer so far.
+    pub landings: u64,
+    /// The part 2 answer so far.
+    pub passes: u64,
+}
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "{}", e),
+            StreamError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for StreamError {}
+
+/// Reads rotations one line at a time, so the whole input never has to be in memory. Calls
+/// `checkpoint` with the totals every `every` rotations, and returns the totals at the end.
+pub fn stream(
+    mut reader: impl BufRead,
+    every: Option<u64>,
+    mut checkpoint: impl FnMut(&Totals),
+) -> Result<Totals, StreamError> {
+    let mut dial = Dial::new(100, 50, &[0]);
+    let mut totals = Totals {
+        position: dial.position(),
+        ..Totals::default()
+    };
+    let mut text = String::new();
+    let mut number = 0;
+    loop {
+        text.clear();
+        if reader.read_line(&mut text).map_err(StreamError::Io)? == 0 {
+            return Ok(totals);
+        }
+        number += 1;
+        let line = Line {
+            number,
+            text: text.trim_end_matches(['\n', '\r']),
+        };
+        dial.rotate(parse_line(line).map_err(StreamError::Parse)?);
+        let counts = dial.counts(0).unwrap();
+        totals = Totals {
+            rotations: totals.rotations + 1,
+            position: dial.position(),
+            landings: counts.landings,
+            passes: counts.passes,
+        };
+        if every.is_some_and(|every| totals.rotations.is_multiple_of(every)) {
+            checkpoint(&totals);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BASIC_INPUT, StreamError, Totals, stream};
+
+    #[test]
+    fn streaming_matches_parts() {
+        let mut checkpoints = vec![];
+        let totals = stream(BASIC_INPUT.as_bytes(), Some(4), |t| checkpoints.push(*t)).unwrap();
+        assert_eq!(
+            totals,
+            Totals {
+                rotations: 10,
+                position: 32,
+                landings: 3,
+                passes: 6
+            }
+        );
+        let rotations: Vec<u64> = checkpoints.iter().map(|t| t.rotations).collect();
+        assert_eq!(rotations, vec![4, 8]);
+        assert_eq!((checkpoints[0].landings, checkpoints[0].passes), (1, 2));
+    }
+
+    #[test]
+    fn stream_errors_have_line_numbers() {
+        let err = stream("L68\r\nR5\r\nX3\r\n".as_bytes(), None, |_| {}).unwrap_err();
+        let StreamError::Parse(err) = err else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((err.line, err.column), (3, 1));
+    }
+}
@@ -0,0 +1,121 @@
+use crate::Dial;
+
+/// What one rotation of the safe dial did.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TraceStep {
+    pub rotation: i64,
+    pub start: i64,
+    pub end: i64,
+    pub zero_passes: u64,
+    pub landed_on_zero: bool,
+}
+
+/// Every rotation of the safe dial, starting from 50.
+pub fn trace(rotations: &[i64]) -> impl Iterator<Item = TraceStep> + '_ {
+    let mut dial = Dial::new(100, 50, &[0]);
+    rotations.iter().map(move |rotation| {
+        let start = dial.position();
+        let before = dial.counts(0).unwrap();
+        dial.rotate(*rotation);
+        let after = dial.counts(0).unwrap();
+        TraceStep {
+            rotation: *rotation,
+            start,
+            end: dial.position(),
+            zero_passes: after.passes - before.passes,
+            landed_on_zero: after.landings > before.landings,
+        }
+    })
+}
+
+/// The trace as CSV, with a header row and steps numbered from 1.
+pub fn trace_csv(steps: impl IntoIterator<Item = TraceStep>) -> String {
+    let mut csv = String::from("step,rotation,start,end,zero_passes,landed_on_zero\n");
+    for (i, step) in steps.into_iter().enumerate() {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            i + 1,
+            step.rotation,
+            step.start,
+            step.end,
+            step.zero_passes,
+            step.landed_on_zero
+        ));
+    }
+    csv
+}
+
+/// The trace as a JSON array with one object per step, numbered from 1.
+pub fn trace_json(steps: impl IntoIterator<Item = TraceStep>) -> String {
+    let objects: Vec<String> = steps
+        .into_iter()
+        .enumerate()
+        .map(|(i, step)| {
+            format!(
+                "  {{\"step\": {}, \"rotation\": {}, \"start\": {}, \"end\": {}, \"zero_passes\": {}, \"landed_on_zero\": {}}}",
+                i + 1,
+                step.rotation,
+                step.start,
+                step.end,
+                step.zero_passes,
+                step.landed_on_zero
+            )
+        })
+        .collect();
+    if objects.is_empty() {
+        return "[]\n".to_string();
+    }
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BASIC_INPUT, TraceStep, parse_input, trace, trace_csv, trace_json};
+
+    #[test]
+    fn trace_matches_totals() {
+        let rotations = parse_input(BASIC_INPUT).unwrap();
+        let steps: Vec<TraceStep> = trace(&rotations).collect();
+        assert_eq!(steps.len(), rotations.len());
+        assert_eq!(steps.iter().filter(|s| s.landed_on_zero).count(), 3);
+        assert_eq!(steps.iter().map(|s| s.zero_passes).sum::<u64>(), 6);
+        assert!(steps.windows(2).all(|w| w[0].end == w[1].start));
+    }
+
+    #[test]
+    fn leaving_zero_leftwards_is_not_a_pass() {
+        let steps: Vec<TraceStep> = trace(&[-50, -5, 305]).collect();
+        assert_eq!(
+            steps[1],
+            TraceStep {
+                rotation: -5,
+                start: 0,
+                end: 95,
+                zero_passes: 0,
+                landed_on_zero: false
+            }
+        );
+        assert_eq!(steps[2].zero_passes, 4);
+    }
+
+    #[test]
+    fn exports() {
+        let steps: Vec<TraceStep> = trace(&[-50, 120]).collect();
+        assert_eq!(
+            trace_csv(steps.iter().copied()),
+            "step,rotation,start,end,zero_passes,landed_on_zero
+1,-50,50,0,1,true
+2,120,0,20,1,false
+"
+        );
+        assert_eq!(
+            trace_json(steps),
+            r#"[
+  {"step": 1, "rotation": -50, "start": 50, "end": 0, "zero_passes": 1, "landed_on_zero": true},
+  {"step": 2, "rotation": 120, "start": 0, "end": 20, "zero_passes": 1, "landed_on_zero": false}
+]
+"#
+        );
+        assert_eq!(trace_json(vec![]), "[]\n");
+    }
+}
@@ -0,0 +1,589 @@
+use common::{Example, Line, ParseError, Solution, lines};
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    error::Error,
+    fmt::{self, Display},
+    ops::RangeInclusive,
+};
+
+fn parse_range(
+    line: &Line,
+    r: &str,
+    reject_reversed: bool,
+) -> Result<RangeInclusive<u64>, ParseError> {
+    let (first, last) = line.split_once(r, "-")?;
+    let first_id = line.parse(first, "the first ID of a range")?;
+    let last_id = line.parse(last, "the last ID of a range")?;
+    if reject_reversed && last_id < first_id {
+        return Err(line.error(last, format!("an ID no smaller than {}", first_id)));
+    }
+    Ok(first_id..=last_id)
+}
+
+fn parse_input(input: &str, normalize: bool) -> Result<Vec<RangeInclusive<u64>>, ParseError> {
+    let mut ranges = vec![];
+    for line in lines(input) {
+        let text = line.text.trim_end();
+        if text.is_empty() {
+            continue;
+        }
+        for r in text.split(',') {
+            ranges.push(parse_range(&line, r, normalize)?);
+        }
+    }
+    if normalize {
+        ranges = normalize_ranges(ranges);
+    }
+    Ok(ranges)
+}
+
+/// Sorts the ranges and merges any that overlap or touch, so no ID is in more than one.
+pub fn normalize_ranges(mut ranges: Vec<RangeInclusive<u64>>) -> Vec<RangeInclusive<u64>> {
+    ranges.retain(|r| !r.is_empty());
+    ranges.sort_by_key(|r| *r.start());
+    let mut merged: Vec<RangeInclusive<u64>> = vec![];
+    for r in ranges {
+        match merged.last_mut() {
+            Some(last) if *r.start() <= last.end().saturating_add(1) => {
+                *last = *last.start()..=*last.end().max(r.end());
+            }
+            _ => merged.push(r),
+        }
+    }
+    merged
+}
+
+/// Ranges in the puzzle's input format.
+pub fn format_ranges(ranges: &[RangeInclusive<u64>]) -> String {
+    ranges
+        .iter()
+        .map(|r| format!("{}-{}", r.start(), r.end()))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// How many invalid IDs there are and what they add up to. Sums over ranges near u64::MAX
+/// don't fit in a u64.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Total {
+    pub count: u128,
+    pub sum: u128,
+}
+
+impl Total {
+    fn add(self, other: Total) -> Total {
+        Total {
+            count: self.count + other.count,
+            sum: self.sum + other.sum,
+        }
+    }
+
+    fn sub(self, other: Total) -> Total {
+        Total {
+            count: self.count - other.count,
+            sum: self.sum - other.sum,
+        }
+    }
+}
+
+/// How many times a pattern has to repeat for an ID to be invalid.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Repeats {
+    Exactly(u32),
+    AtLeast(u32),
+    Prime,
+}
+
+impl Repeats {
+    fn allows(&self, repeats: u32) -> bool {
+        match self {
+            Repeats::Exactly(n) => repeats == *n,
+            Repeats::AtLeast(n) => repeats >= *n,
+            Repeats::Prime => repeats >= 2 && (2..repeats).all(|d| !repeats.is_multiple_of(d)),
+        }
+    }
+}
+
+/// Which IDs are invalid: those whose digits in `base` are a pattern repeated a number of
+/// times `repeats` allows. With `leading_zeros`, IDs can be padded with zeros to make the
+/// repeat, so `101` is `01` twice.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rule {
+    repeats: Repeats,
+    // At least 2, checked by `Rule::new`
+    base: u32,
+    leading_zeros: bool,
+}
+
+/// A base with too few digits to write IDs in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BaseTooSmall {
+    pub base: u32,
+}
+
+impl Display for BaseTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "base {} has too few digits, it needs at least 2",
+            self.base
+        )
+    }
+}
+
+impl Error for BaseTooSmall {}
+
+impl Rule {
+    pub fn new(repeats: Repeats, base: u32, leading_zeros: bool) -> Result<Rule, BaseTooSmall> {
+        if base < 2 {
+            return Err(BaseTooSmall { base });
+        }
+        Ok(Rule {
+            repeats,
+            base,
+            leading_zeros,
+        })
+    }
+
+    /// Part 1's rule, an ID made of the same digits twice.
+    pub const PART1: Rule = Rule {
+        repeats: Repeats::Exactly(2),
+        base: 10,
+        leading_zeros: false,
+    };
+
+    /// Part 2's rule, an ID made of the same digits twice or more.
+    pub const PART2: Rule = Rule {
+        repeats: Repeats::AtLeast(2),
+        base: 10,
+        leading_zeros: false,
+    };
+
+    pub fn repeats(&self) -> Repeats {
+        self.repeats
+    }
+
+    pub fn base(&self) -> u32 {
+        self.base
+    }
+
+    pub fn leading_zeros(&self) -> bool {
+        self.leading_zeros
+    }
+
+    // Enough digits for any u64
+    fn max_digits(&self) -> u32 {
+        let mut digits = 1;
+        while (self.base as u128).pow(digits) <= u64::MAX as u128 {
+            digits += 1;
+        }
+        digits
+    }
+
+    // The `digits`-long IDs in the range that are a `period`-long pattern repeated are the
+    // patterns times 1 0..01 0..01 .., so they're an arithmetic sequence. Returns the
+    // multiplier and the first and last patterns, if any fall in the range.
+    fn patterns(
+        &self,
+        range: &RangeInclusive<u64>,
+        digits: u32,
+        period: u32,
+    ) -> Option<(u128, u128, u128)> {
+        let base = self.base as u128;
+        let multiplier = (base.pow(digits) - 1) / (base.pow(period) - 1);
+        let (smallest_id, smallest_pattern) = if self.leading_zeros {
+            (1, 1)
+        } else {
+            (base.pow(digits - 1), base.pow(period - 1))
+        };
+        let first = (*range.start() as u128).max(smallest_id);
+        let last = (*range.end() as u128).min(base.pow(digits) - 1);
+        let first_pattern = first.div_ceil(multiplier).max(smallest_pattern);
+        let last_pattern = (last / multiplier).min(base.pow(period) - 1);
+        if first > last || first_pattern > last_pattern {
+            return None;
+        }
+        Some((multiplier, first_pattern, last_pattern))
+    }
+
+    fn with_period(&self, range: &RangeInclusive<u64>, digits: u32, period: u32) -> Total {
+        let Some((multiplier, first_pattern, last_pattern)) = self.patterns(range, digits, period)
+        else {
+            return Total::default();
+        };
+        let count = last_pattern - first_pattern + 1;
+        Total {
+            count,
+            sum: (first_pattern + last_pattern) * count / 2 * multiplier,
+        }
+    }
+
+    /// Every invalid ID in a range, smallest first, without visiting the valid ones.
+    pub fn invalid_ids(&self, range: &RangeInclusive<u64>) -> InvalidIds {
+        let mut sequences = BinaryHeap::new();
+        for digits in 2..=self.max_digits() {
+            for period in (1..digits).filter(|p| digits.is_multiple_of(*p)) {
+                if !self.repeats.allows(digits / period) {
+                    continue;
+                }
+                if let Some((multiplier, first, last)) = self.patterns(range, digits, period) {
+                    sequences.push(Reverse((first * multiplier, multiplier, last * multiplier)));
+                }
+            }
+        }
+        InvalidIds {
+            sequences,
+            previous: None,
+        }
+    }
+
+    /// The invalid IDs in a range. A `digits`-long ID whose shortest pattern is `d` long
+    /// repeats every pattern whose length is a multiple of `d` dividing `digits`, so adding up
+    /// each period would count `111111` three times. Instead count the IDs by their shortest
+    /// pattern, taking off the ones already counted for lengths dividing it, and keep those
+    /// that can be split into an allowed number of repeats.
+    pub fn total(&self, range: &RangeInclusive<u64>) -> Total {
+        let mut total = Total::default();
+        for digits in 2..=self.max_digits() {
+            let mut shortest = vec![Total::default(); digits as usize];
+            for period in (1..digits).filter(|p| digits.is_multiple_of(*p)) {
+                let mut exact = self.with_period(range, digits, period);
+                for shorter in (1..period).filter(|s| period.is_multiple_of(*s)) {
+                    exact = exact.sub(shortest[shorter as usize]);
+                }
+                shortest[period as usize] = exact;
+                let most_repeats = digits / period;
+                if (2..=most_repeats)
+                    .any(|r| most_repeats.is_multiple_of(r) && self.repeats.allows(r))
+                {
+                    total = total.add(exact);
+                }
+            }
+        }
+        total
+    }
+}
+
+/// The invalid IDs in a range, merged from the sequences of repeats of each length, since an
+/// ID like `111111` is in several.
+#[derive(Clone, Debug)]
+pub struct InvalidIds {
+    // (next ID, step, last ID) for each sequence
+    sequences: BinaryHeap<Reverse<(u128, u128, u128)>>,
+    previous: Option<u128>,
+}
+
+impl Iterator for InvalidIds {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while let Some(Reverse((id, step, last))) = self.sequences.pop() {
+            if id < last {
+                self.sequences.push(Reverse((id + step, step, last)));
+            }
+            if self.previous != Some(id) {
+                self.previous = Some(id);
+                return Some(id as u64);
+            }
+        }
+        None
+    }
+}
+
+/// The invalid IDs in one input range.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RangeReport {
+    pub range: RangeInclusive<u64>,
+    // The rule `total` was counted with, so the listing always agrees with it
+    pub rule: Rule,
+    pub total: Total,
+}
+
+impl RangeReport {
+    pub fn invalid_ids(&self) -> InvalidIds {
+        self.rule.invalid_ids(&self.range)
+    }
+}
+
+/// The count and sum of invalid IDs in each range.
+pub fn range_reports(ranges: &[RangeInclusive<u64>], rule: &Rule) -> Vec<RangeReport> {
+    ranges
+        .iter()
+        .map(|range| RangeReport {
+            range: range.clone(),
+            rule: *rule,
+            total: rule.total(range),
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Config {
+    pub part1: Rule,
+    pub part2: Rule,
+    // Merge overlapping ranges so shared IDs only count once, and reject reversed ranges
+    pub normalize: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            part1: Rule::PART1,
+            part2: Rule::PART2,
+            normalize: false,
+        }
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Config = Config;
+    type Input = Vec<RangeInclusive<u64>>;
+    type Answer1 = u128;
+    type Answer2 = u128;
+
+    fn parse(input: &str, config: &Config) -> Result<Self::Input, ParseError> {
+        parse_input(input, config.normalize)
+    }
+
+    fn part1(ranges: &Self::Input, config: &Config) -> u128 {
+        ranges.iter().map(|r| config.part1.total(r).sum).sum()
+    }
+
+    fn part2(ranges: &Self::Input, config: &Config) -> u128 {
+        ranges.iter().map(|r| config.part2.total(r).sum).sum()
+    }
+
+    fn examples() -> Vec<Example<Config>> {
+        vec![Example {
+            name: "basic",
+            input: BASIC_INPUT,
+            config: Config::default(),
+            parts: &[1, 2],
+        }]
+    }
+}
+
+pub fn part1(input: &str) -> u128 {
+    Day02::solve_part1(input, &Config::default()).unwrap()
+}
+
+pub fn part2(input: &str) -> u128 {
+    Day02::solve_part2(input, &Config::default()).unwrap()
+}
+
+pub const BASIC_INPUT: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BASIC_INPUT, BaseTooSmall, Config, Day02, Repeats, Rule, Total, format_ranges, part1,
+        part2, range_reports,
+    };
+    use common::Solution;
+    use std::ops::RangeInclusive;
+
+    fn is_valid_id(id: u64) -> bool {
+        let s = id.to_string();
+        if !s.len().is_multiple_of(2) {
+            true
+        } else {
+            s[0..s.len() / 2] != s[s.len() / 2..]
+        }
+    }
+
+    fn is_repeat_of(s: &str, pat: &str) -> bool {
+        let step = pat.len();
+        if !s.len().is_multiple_of(step) {
+            return false;
+        }
+        for i in (step..s.len()).step_by(step) {
+            if &s[i..i + step] != pat {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn is_valid_id_full(id: u64) -> bool {
+        let s = id.to_string();
+        for i in 1..=s.len() / 2 {
+            if is_repeat_of(&s, &s[0..i]) {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn brute_force(range: RangeInclusive<u64>, is_valid: fn(u64) -> bool) -> Total {
+        range
+            .filter(|id| !is_valid(*id))
+            .fold(Total::default(), |total, id| Total {
+                count: total.count + 1,
+                sum: total.sum + id as u128,
+            })
+    }
+
+    #[test]
+    fn test_is_valid_id() {
+        assert!(is_valid_id(12));
+        assert!(is_valid_id(121));
+        assert!(is_valid_id(121212));
+        assert!(is_valid_id(11111112));
+        assert!(is_valid_id(111));
+        assert!(!is_valid_id(11));
+        assert!(!is_valid_id(12341234));
+    }
+
+    #[test]
+    fn basic_test_part1() {
+        assert_eq!(part1(BASIC_INPUT), 1227775554)
+    }
+
+    #[test]
+    fn test_is_valid_id_full() {
+        assert!(is_valid_id_full(12));
+        assert!(is_valid_id_full(121));
+        assert!(is_valid_id_full(11111112));
+        assert!(!is_valid_id_full(121212));
+        assert!(!is_valid_id_full(11));
+        assert!(!is_valid_id_full(111));
+        assert!(!is_valid_id_full(12341234));
+        assert!(!is_valid_id_full(123123123));
+    }
+
+    #[test]
+    fn basic_test_part2() {
+        assert_eq!(part2(BASIC_INPUT), 4174379265);
+    }
+
+    #[test]
+    fn enumeration_matches_brute_force() {
+        for (first, last) in [
+            (0, 20_000),
+            (95, 115),
+            (99_990, 1_001_000),
+            (111_110, 111_112),
+        ] {
+            assert_eq!(
+                Rule::PART1.total(&(first..=last)),
+                brute_force(first..=last, is_valid_id)
+            );
+            assert_eq!(
+                Rule::PART2.total(&(first..=last)),
+                brute_force(first..=last, is_valid_id_full)
+            );
+        }
+    }
+
+    // Whether some zero padding of the ID's digits, or none, is an allowed number of repeats
+    fn breaks_rule(rule: &Rule, id: u64) -> bool {
+        let mut digits = vec![];
+        let mut rest = id;
+        while rest > 0 {
+            digits.insert(0, rest % rule.base as u64);
+            rest /= rule.base as u64;
+        }
+        let most_padding = if rule.leading_zeros { digits.len() } else { 0 };
+        (0..=most_padding).any(|padding| {
+            let mut padded = vec![0; padding];
+            padded.extend(digits.iter());
+            (1..padded.len()).any(|period| {
+                let repeats = padded.len() / period;
+                padded.len().is_multiple_of(period)
+                    && rule.repeats.allows(repeats as u32)
+                    && padded.chunks(period).all(|c| c == &padded[..period])
+            })
+        })
+    }
+
+    #[test]
+    fn rules_match_brute_force() {
+        let rules = [Repeats::Exactly(2), Repeats::AtLeast(3), Repeats::Prime];
+        for repeats in rules {
+            for base in [2, 10, 16] {
+                for leading_zeros in [false, true] {
+                    let rule = Rule::new(repeats, base, leading_zeros).unwrap();
+                    let range = 1..=12_000;
+                    let expected = range.clone().filter(|id| breaks_rule(&rule, *id)).fold(
+                        Total::default(),
+                        |total, id| Total {
+                            count: total.count + 1,
+                            sum: total.sum + id as u128,
+                        },
+                    );
+                    assert_eq!(rule.total(&range), expected, "{:?}", rule);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rules_need_a_base_of_two_or_more() {
+        for base in [0, 1] {
+            assert_eq!(
+                Rule::new(Repeats::Exactly(2), base, false),
+                Err(BaseTooSmall { base })
+            );
+        }
+        assert_eq!(Rule::new(Repeats::AtLeast(2), 10, false), Ok(Rule::PART2));
+    }
+
+    #[test]
+    fn normalizing_merges_overlaps() {
+        let input = "95-115,100-120\n5-7,8-9,200-200,130-125";
+        let normalize = Config {
+            normalize: true,
+            ..Config::default()
+        };
+        let err = Day02::parse(input, &normalize).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 21));
+        assert_eq!(err.expected, "an ID no smaller than 130");
+        let input = "95-115,100-120\n5-7,8-9,200-200";
+        let ranges = Day02::parse(input, &normalize).unwrap();
+        assert_eq!(format_ranges(&ranges), "5-9,95-120,200-200");
+        // 111 is in both of the first two ranges
+        assert_eq!(Day02::part2(&ranges, &normalize), 99 + 111);
+        let ranges = Day02::parse(input, &Config::default()).unwrap();
+        assert_eq!(Day02::part2(&ranges, &Config::default()), 99 + 111 * 2);
+    }
+
+    #[test]
+    fn lists_invalid_ids_per_range() {
+        let ranges = Day02::parse(BASIC_INPUT, &Config::default()).unwrap();
+        let reports = range_reports(&ranges, &Rule::PART2);
+        let ids: Vec<u64> = reports[1].invalid_ids().collect();
+        assert_eq!(ids, vec![99, 111]);
+        assert_eq!(reports[1].total, Total { count: 2, sum: 210 });
+        let ids: Vec<u64> = reports[10].invalid_ids().collect();
+        assert_eq!(ids, vec![2121212121]);
+        for report in reports {
+            let ids: Vec<u64> = report.invalid_ids().collect();
+            assert!(ids.is_sorted());
+            assert_eq!(ids.len() as u128, report.total.count);
+            assert_eq!(
+                ids.iter().map(|id| *id as u128).sum::<u128>(),
+                report.total.sum
+            );
+        }
+        // 9 with 2 digits, 9 with 3 and 90 with 4 come first
+        let mut all = Rule::PART2.invalid_ids(&(0..=u64::MAX)).skip(108);
+        assert_eq!(all.next(), Some(11111));
+    }
+
+    #[test]
+    fn whole_u64_range() {
+        // Every pattern up to 9 digits long, and the 10 digit ones that fit below u64::MAX
+        let count = 999_999_999 + (u64::MAX / 10_000_000_001 - 999_999_999) as u128;
+        assert_eq!(Rule::PART1.total(&(0..=u64::MAX)).count, count);
+        let all = Rule::PART2.total(&(0..=u64::MAX));
+        assert!(all.count > count);
+        assert!(all.sum > u64::MAX as u128);
+        assert_eq!(
+            Rule::PART2.total(&(u64::MAX - 1..=u64::MAX)),
+            Total::default()
+        );
+    }
+}
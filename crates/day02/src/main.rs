@@ -1,121 +1,60 @@
-use std::fs::read_to_string;
-use std::ops::RangeInclusive;
-
-fn parse_range(r: &str) -> RangeInclusive<u64> {
-    let mut split = r.split('-');
-    let first = split.next().unwrap();
-    let first = first.parse().unwrap();
-    let last = split.next().unwrap();
-    let last = last.parse().unwrap();
-    first..=last
-}
-
-fn parse_input(input: &str) -> Vec<RangeInclusive<u64>> {
-    input.split(',').map(parse_range).collect()
-}
-
-fn is_valid_id(id: u64) -> bool {
-    let s = id.to_string();
-    if s.len() % 2 != 0 {
-        true
-    } else {
-        s[0..s.len() / 2] != s[s.len() / 2..]
-    }
-}
-
-fn is_repeat_of(s: &str, pat: &str) -> bool {
-    let step = pat.len();
-    if s.len() % step != 0 {
-        return false;
-    }
-    for i in (step..s.len()).step_by(step) {
-        if &s[i..i + step] != pat {
-            return false;
-        }
-    }
-    true
-}
-
-fn is_valid_id_full(id: u64) -> bool {
-    let s = id.to_string();
-    for i in 1..=s.len() / 2 {
-        if is_repeat_of(&s, &s[0..i]) {
-            return false;
+use common::{Solution, parse_or_exit};
+use day02::{BASIC_INPUT, Config, Day02, Rule, format_ranges, range_reports};
+use std::{env, fs::read_to_string, ops::RangeInclusive};
+
+// Most invalid IDs to show for one range in the table
+const LISTED_IDS: usize = 5;
+
+fn print_table(part: u8, ranges: &[RangeInclusive<u64>], rule: &Rule) {
+    println!("part {}", part);
+    println!("{:>23} {:>6} {:>20}  invalid IDs", "range", "count", "sum");
+    for report in range_reports(ranges, rule) {
+        let mut ids: Vec<String> = report
+            .invalid_ids()
+            .take(LISTED_IDS)
+            .map(|id| id.to_string())
+            .collect();
+        if report.total.count > LISTED_IDS as u128 {
+            ids.push(format!(
+                "and {} more",
+                report.total.count - LISTED_IDS as u128
+            ));
         }
+        println!(
+            "{:>23} {:>6} {:>20}  {}",
+            format!("{}-{}", report.range.start(), report.range.end()),
+            report.total.count,
+            report.total.sum,
+            ids.join(", ")
+        );
     }
-    true
-}
-
-fn part1(input: &str) -> u64 {
-    let ranges = parse_input(input);
-    let mut sum = 0;
-    for r in ranges {
-        for id in r {
-            if !is_valid_id(id) {
-                sum += id;
-            }
-        }
-    }
-    sum
-}
-
-fn part2(input: &str) -> u64 {
-    let ranges = parse_input(input);
-    let mut sum = 0;
-    for r in ranges {
-        for id in r {
-            if !is_valid_id_full(id) {
-                sum += id;
-            }
-        }
-    }
-    sum
 }
 
 fn main() {
-    let input = read_to_string("input.txt").unwrap();
-    let input = input.trim();
-    let p1_answer = part1(&input);
-    println!("day 1 part 1: {}", p1_answer);
-    let p2_answer = part2(&input);
-    println!("day 1 part 2: {}", p2_answer);
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::{is_valid_id, is_valid_id_full, part1, part2};
-    const BASIC_INPUT: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
-
-    #[test]
-    fn test_is_valid_id() {
-        assert!(is_valid_id(12));
-        assert!(is_valid_id(121));
-        assert!(is_valid_id(121212));
-        assert!(is_valid_id(11111112));
-        assert!(is_valid_id(111));
-        assert!(!is_valid_id(11));
-        assert!(!is_valid_id(12341234));
-    }
-
-    #[test]
-    fn basic_test_part1() {
-        assert_eq!(part1(BASIC_INPUT), 1227775554)
-    }
-
-    #[test]
-    fn test_is_valid_id_full() {
-        assert!(is_valid_id_full(12));
-        assert!(is_valid_id_full(121));
-        assert!(is_valid_id_full(11111112));
-        assert!(!is_valid_id_full(121212));
-        assert!(!is_valid_id_full(11));
-        assert!(!is_valid_id_full(111));
-        assert!(!is_valid_id_full(12341234));
-        assert!(!is_valid_id_full(123123123));
-    }
-
-    #[test]
-    fn basic_test_part2() {
-        assert_eq!(part2(BASIC_INPUT), 4174379265);
-    }
+    let args: Vec<String> = env::args().skip(1).collect();
+    let flag = |name: &str| args.iter().any(|arg| arg == name);
+    // `--example` uses the puzzle's example instead of input.txt
+    let (path, input) = if flag("--example") {
+        ("example", BASIC_INPUT.to_string())
+    } else {
+        ("input.txt", read_to_string("input.txt").unwrap())
+    };
+    // `--normalize` merges overlapping ranges first, and prints what they merged into
+    let config = Config {
+        normalize: flag("--normalize"),
+        ..Config::default()
+    };
+    let ranges = parse_or_exit::<Day02>(path, &input, &config);
+    if config.normalize {
+        println!("normalized ranges: {}", format_ranges(&ranges));
+    }
+    // `--list` shows the invalid IDs in each range
+    if flag("--list") {
+        print_table(1, &ranges, &config.part1);
+        print_table(2, &ranges, &config.part2);
+    }
+    let p1_answer = Day02::part1(&ranges, &config);
+    println!("day 2 part 1: {}", p1_answer);
+    let p2_answer = Day02::part2(&ranges, &config);
+    println!("day 2 part 2: {}", p2_answer);
 }
@@ -0,0 +1,205 @@
+use crate::{Joltage, TooFewBatteries, check_batteries, largest_indices};
+use common::{ParseError, lines};
+use std::{ops::Range, thread};
+
+/// Banks with one byte per battery, all in one buffer.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Banks {
+    digits: Vec<u8>,
+    // Where each bank ends in `digits`
+    ends: Vec<usize>,
+}
+
+impl Banks {
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    pub fn get(&self, i: usize) -> &[u8] {
+        let start = if i == 0 { 0 } else { self.ends[i - 1] };
+        &self.digits[start..self.ends[i]]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &[u8]> {
+        (0..self.len()).map(|i| self.get(i))
+    }
+
+    fn append(&mut self, other: Banks) {
+        let offset = self.digits.len();
+        self.digits.extend(other.digits);
+        self.ends.extend(other.ends.iter().map(|end| end + offset));
+    }
+}
+
+// Every bank needs at least `batteries` batteries
+fn parse_piece(input: &str, batteries: usize) -> Result<Banks, ParseError> {
+    let mut banks = Banks::default();
+    for line in lines(input).filter(|line| !line.text.trim().is_empty()) {
+        let start = banks.digits.len();
+        for (i, c) in line.text.char_indices() {
+            let digit = c
+                .to_digit(10)
+                .ok_or_else(|| line.error(&line.text[i..i + c.len_utf8()], "a digit"))?;
+            banks.digits.push(digit as u8);
+        }
+        if banks.digits.len() - start < batteries {
+            return Err(line.end_error(format!("at least {} batteries", batteries)));
+        }
+        banks.ends.push(banks.digits.len());
+    }
+    Ok(banks)
+}
+
+// Up to `pieces` pieces of about the same size, each ending at the end of a line
+fn split_lines(input: &str, pieces: usize) -> Vec<Range<usize>> {
+    let size = input.len().div_ceil(pieces.max(1)).max(1);
+    let mut ranges = vec![];
+    let mut start = 0;
+    while start < input.len() {
+        // Bytes, not str, since the cut can land inside a character of malformed input
+        let end = input.as_bytes()[(start + size).min(input.len())..]
+            .iter()
+            .position(|b| *b == b'\n')
+            .map_or(input.len(), |i| start + size + i + 1);
+        ranges.push(start..end);
+        start = end;
+    }
+    ranges
+}
+
+/// Parses the banks on `threads` threads, each taking a piece of the input.
+pub fn parse_banks(input: &str, batteries: usize, threads: usize) -> Result<Banks, ParseError> {
+    let ranges = split_lines(input, threads);
+    let pieces: Vec<Result<Banks, ParseError>> = thread::scope(|s| {
+        let handles: Vec<_> = ranges
+            .iter()
+            .map(|range| s.spawn(|| parse_piece(&input[range.clone()], batteries)))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    let mut banks = Banks::default();
+    for (range, piece) in ranges.iter().zip(pieces) {
+        match piece {
+            Ok(piece) => banks.append(piece),
+            Err(e) => return Err(e.below(input[..range.start].matches('\n').count())),
+        }
+    }
+    Ok(banks)
+}
+
+// Adds the largest joltage from `k` batteries in the bank to the total
+fn add_largest(total: &mut Joltage, bank: &[u8], k: usize) -> Result<(), TooFewBatteries> {
+    check_batteries(bank, k)?;
+    let indices = largest_indices(bank, k);
+    total.add(indices.iter().map(|i| bank[*i] as u64));
+    Ok(())
+}
+
+/// The sum of the largest joltages from `k` batteries in each bank, split across `threads`
+/// threads.
+pub fn total_joltage(banks: &Banks, k: usize, threads: usize) -> Result<Joltage, TooFewBatteries> {
+    let per_thread = banks.len().div_ceil(threads.max(1)).max(1);
+    let totals: Vec<Result<Joltage, TooFewBatteries>> = thread::scope(|s| {
+        let handles: Vec<_> = (0..banks.len())
+            .step_by(per_thread)
+            .map(|start| {
+                s.spawn(move || {
+                    let mut total = Joltage::default();
+                    for i in start..(start + per_thread).min(banks.len()) {
+                        add_largest(&mut total, banks.get(i), k)?;
+                    }
+                    Ok(total)
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    let mut total = Joltage::default();
+    for t in totals {
+        total.add(t?.digits.into_iter());
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BASIC_INPUT, TooFewBatteries, banks::split_lines, parse_banks, total_joltage};
+
+    #[test]
+    fn threads_agree() {
+        let input = format!("{}\n", BASIC_INPUT)
+            .repeat(7)
+            .replace("1\n", "1\n\n")
+            + "\n";
+        let one = parse_banks(&input, 12, 1).unwrap();
+        assert_eq!(one.len(), 28);
+        assert_eq!(one.get(1), &[8, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 9]);
+        for threads in 2..10 {
+            let banks = parse_banks(&input, 12, threads).unwrap();
+            assert_eq!(banks, one);
+            let total = |k| total_joltage(&banks, k, threads).unwrap().value();
+            assert_eq!(total(2), Some(357 * 7));
+            assert_eq!(total(12), Some(3121910778619 * 7));
+        }
+        assert_eq!(
+            total_joltage(&one, 16, 3),
+            Err(TooFewBatteries {
+                batteries: 15,
+                wanted: 16
+            })
+        );
+        assert!(parse_banks("", 12, 4).unwrap().is_empty());
+    }
+
+    #[test]
+    fn totals_too_big_for_u128() {
+        let input = "9".repeat(50) + "\n" + &"9".repeat(40) + "\n";
+        for threads in 1..3 {
+            let banks = parse_banks(&input, 40, threads).unwrap();
+            let total = total_joltage(&banks, 40, threads).unwrap();
+            assert_eq!(total.to_string(), format!("1{}8", "9".repeat(39)));
+            assert_eq!(total.value(), None);
+            let banks = parse_banks("0000\n0001\n", 4, threads).unwrap();
+            assert_eq!(total_joltage(&banks, 4, threads).unwrap().to_string(), "1");
+            assert_eq!(total_joltage(&banks, 0, threads).unwrap().to_string(), "0");
+        }
+    }
+
+    #[test]
+    fn pieces_end_at_line_ends() {
+        let input = "12\n345\n6\n\n7890";
+        for pieces in 1..8 {
+            let ranges = split_lines(input, pieces);
+            assert!(ranges.len() <= pieces);
+            assert_eq!(ranges.first().unwrap().start, 0);
+            assert_eq!(ranges.last().unwrap().end, input.len());
+            assert!(ranges.windows(2).all(|w| w[0].end == w[1].start));
+            assert!(
+                ranges
+                    .iter()
+                    .all(|r| r.start == 0 || &input[r.start - 1..r.start] == "\n")
+            );
+        }
+    }
+
+    #[test]
+    fn errors_count_lines_across_pieces() {
+        let input = format!("{}\n", BASIC_INPUT)
+            .repeat(3)
+            .replace("1\n", "1\n\n")
+            + "\n81818191x112111\n";
+        for threads in 1..6 {
+            let err = parse_banks(&input, 12, threads).unwrap_err();
+            assert_eq!((err.line, err.column), (20, 9), "{} threads", threads);
+        }
+        let input = "123456789012\u{e9}4\n123456789012\n".repeat(3);
+        for threads in 1..8 {
+            let err = parse_banks(&input, 12, threads).unwrap_err();
+            assert_eq!((err.line, err.column), (1, 13), "{} threads", threads);
+        }
+    }
+}
@@ -0,0 +1,635 @@
+use common::{Example, ParseError, Solution};
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    iter, thread,
+};
+
+mod banks;
+
+pub use banks::{Banks, parse_banks, total_joltage};
+
+/// The banks as one digit per `u64`, which is easier to work with than `Banks` but eight times
+/// the size. Every bank needs at least `batteries` batteries.
+pub fn parse_input(input: &str, batteries: usize) -> Result<Vec<Vec<u64>>, ParseError> {
+    let banks = parse_banks(input, batteries, 1)?;
+    Ok(banks.iter().map(to_u64s).collect())
+}
+
+pub fn to_u64s(bank: &[u8]) -> Vec<u64> {
+    bank.iter().map(|d| *d as u64).collect()
+}
+
+/// The batteries to turn on in a bank for the largest `k`-digit joltage, in increasing order.
+/// A stack of the chosen batteries so far, popping any smaller than the next one while there
+/// are still batteries to spare, so each battery is pushed and popped at most once.
+fn largest_indices<T: PartialOrd>(bank: &[T], k: usize) -> Vec<usize> {
+    let mut spare = bank.len() - k;
+    let mut chosen: Vec<usize> = Vec::with_capacity(k);
+    for (i, digit) in bank.iter().enumerate() {
+        while spare > 0 && chosen.last().is_some_and(|&last| bank[last] < *digit) {
+            chosen.pop();
+            spare -= 1;
+        }
+        if chosen.len() < k {
+            chosen.push(i);
+        } else {
+            spare -= 1;
+        }
+    }
+    chosen
+}
+
+/// The digits of a joltage, or of a total of them. Any number of batteries can be turned on, so
+/// this can be too big for a `u128`.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Joltage {
+    pub digits: Vec<u64>,
+}
+
+impl Joltage {
+    /// The joltage as a number, if it fits.
+    pub fn value(&self) -> Option<u128> {
+        self.digits
+            .iter()
+            .try_fold(0u128, |num, d| num.checked_mul(10)?.checked_add(*d as u128))
+    }
+
+    // Adds a number given as its digits, dropping any leading zeros from the result
+    fn add(&mut self, digits: impl DoubleEndedIterator<Item = u64> + ExactSizeIterator) {
+        if digits.len() > self.digits.len() {
+            let extra = digits.len() - self.digits.len();
+            self.digits.splice(0..0, iter::repeat_n(0, extra));
+        }
+        let mut digits = digits.rev();
+        let mut carry = 0;
+        for d in self.digits.iter_mut().rev() {
+            let sum = *d + digits.next().unwrap_or(0) + carry;
+            *d = sum % 10;
+            carry = sum / 10;
+        }
+        if carry > 0 {
+            self.digits.insert(0, carry);
+        }
+        let zeros = self.digits.iter().take_while(|d| **d == 0).count();
+        self.digits.drain(..zeros);
+    }
+}
+
+impl Display for Joltage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.digits.is_empty() {
+            return write!(f, "0");
+        }
+        self.digits.iter().try_for_each(|d| write!(f, "{}", d))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TooFewBatteries {
+    pub batteries: usize,
+    pub wanted: usize,
+}
+
+impl Display for TooFewBatteries {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "can't turn on {} batteries in a bank of {}",
+            self.wanted, self.batteries
+        )
+    }
+}
+
+impl Error for TooFewBatteries {}
+
+/// Which batteries in a bank to turn on, and the joltage that gives.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Selection {
+    pub indices: Vec<usize>,
+    pub joltage: Joltage,
+    /// The bank, with a line under it marking the batteries turned on.
+    pub highlighted: String,
+}
+
+impl Selection {
+    fn new(bank: &[u64], indices: Vec<usize>) -> Selection {
+        let digits: String = bank.iter().map(|d| d.to_string()).collect();
+        let mut marks = vec![' '; bank.len()];
+        indices.iter().for_each(|i| marks[*i] = '^');
+        let marks: String = marks.into_iter().collect();
+        Selection {
+            joltage: Joltage {
+                digits: indices.iter().map(|i| bank[*i]).collect(),
+            },
+            highlighted: format!("{}\n{}", digits, marks.trim_end()),
+            indices,
+        }
+    }
+}
+
+impl Display for Selection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let indices: Vec<String> = self.indices.iter().map(|i| i.to_string()).collect();
+        writeln!(f, "{} from batteries {}", self.joltage, indices.join(","))?;
+        writeln!(f, "{}", self.highlighted)
+    }
+}
+
+fn check_batteries<T>(bank: &[T], k: usize) -> Result<(), TooFewBatteries> {
+    if bank.len() < k {
+        return Err(TooFewBatteries {
+            batteries: bank.len(),
+            wanted: k,
+        });
+    }
+    Ok(())
+}
+
+/// The batteries to turn on for the largest joltage from exactly `k` batteries in a bank.
+pub fn largest_selection(bank: &[u64], k: usize) -> Result<Selection, TooFewBatteries> {
+    check_batteries(bank, k)?;
+    Ok(Selection::new(bank, largest_indices(bank, k)))
+}
+
+/// The largest joltage from turning on exactly `k` batteries in a bank.
+pub fn largest_joltage(bank: &[u64], k: usize) -> Result<Joltage, TooFewBatteries> {
+    largest_selection(bank, k).map(|s| s.joltage)
+}
+
+/// At most `max` batteries turned on in each run of `size` batteries, counting from the start
+/// of the bank.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Block {
+    pub size: usize,
+    pub max: usize,
+}
+
+/// Wiring limits on which batteries in a bank can be turned on together.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Constraints {
+    /// How far apart turned on batteries have to be, so 1 allows neighbours.
+    pub min_gap: usize,
+    pub forbidden: Vec<usize>,
+    pub block: Option<Block>,
+}
+
+impl Default for Constraints {
+    fn default() -> Self {
+        Constraints {
+            min_gap: 1,
+            forbidden: vec![],
+            block: None,
+        }
+    }
+}
+
+/// The batteries to turn on for the largest joltage from exactly `k` batteries in a bank,
+/// within the constraints, or `None` if no `k` batteries meet them.
+pub fn constrained_selection(
+    bank: &[u64],
+    k: usize,
+    constraints: &Constraints,
+) -> Option<Selection> {
+    let n = bank.len();
+    let Block {
+        size: block_size,
+        max: block_max,
+    } = constraints.block.unwrap_or(Block {
+        size: n.max(1),
+        max: k,
+    });
+    assert!(constraints.min_gap > 0 && block_size > 0);
+    let mut allowed = vec![true; n];
+    for i in constraints.forbidden.iter().filter(|i| **i < n) {
+        allowed[*i] = false;
+    }
+    // A block never has more than k batteries on, so counts above that never happen
+    let counts = block_max.min(k) + 1;
+    let count_at = |from: usize, to: usize, count: usize| {
+        if to < n && to / block_size == from / block_size {
+            count.min(counts - 1)
+        } else {
+            0
+        }
+    };
+    // Ties go to the first argument, so the earliest batteries win like in `largest_indices`
+    let larger = |a: Option<Vec<usize>>, b: Option<Vec<usize>>| match (&a, &b) {
+        (Some(x), Some(y)) if y.iter().map(|i| bank[*i]).gt(x.iter().map(|i| bank[*i])) => b,
+        (Some(_), _) => a,
+        _ => b,
+    };
+    // best[i][r][c] is the batteries to turn on for the largest r digits from the ith battery
+    // onwards, when c batteries before it in its block are already on
+    let mut best = vec![vec![vec![None; counts]; k + 1]; n + 1];
+    for row in best.iter_mut() {
+        row[0] = vec![Some(vec![]); counts];
+    }
+    for i in (0..n).rev() {
+        for r in 1..=k {
+            for c in 0..counts {
+                let skip = best[i + 1][r][count_at(i, i + 1, c)].clone();
+                let take = if allowed[i] && c < block_max {
+                    let next = (i + constraints.min_gap).min(n);
+                    best[next][r - 1][count_at(i, next, c + 1)]
+                        .as_ref()
+                        .map(|rest: &Vec<usize>| [&[i], &rest[..]].concat())
+                } else {
+                    None
+                };
+                best[i][r][c] = larger(take, skip);
+            }
+        }
+    }
+    let indices = best[0][k][0].take()?;
+    Some(Selection::new(bank, indices))
+}
+
+/// The batteries to turn on for the smallest joltage from exactly `k` batteries in a bank,
+/// starting with a nonzero digit unless `leading_zero`. `None` if there aren't `k` batteries,
+/// or no way to start with a nonzero digit.
+pub fn smallest_selection(bank: &[u64], k: usize, leading_zero: bool) -> Option<Selection> {
+    if bank.len() < k {
+        return None;
+    }
+    let mut start = 0;
+    let mut indices = vec![];
+    if !leading_zero && k > 0 {
+        // The earliest of the smallest nonzero digits that leaves enough batteries after it
+        let first = (0..=bank.len() - k)
+            .filter(|i| bank[*i] != 0)
+            .min_by_key(|i| bank[*i])?;
+        indices.push(first);
+        start = first + 1;
+    }
+    // The same stack as `largest_indices`, popping larger batteries instead of smaller
+    let wanted = k - indices.len();
+    let mut spare = bank.len() - start - wanted;
+    for (i, digit) in bank.iter().enumerate().skip(start) {
+        while spare > 0 && indices.len() > k - wanted && bank[*indices.last().unwrap()] > *digit {
+            indices.pop();
+            spare -= 1;
+        }
+        if indices.len() < k {
+            indices.push(i);
+        } else {
+            spare -= 1;
+        }
+    }
+    Some(Selection::new(bank, indices))
+}
+
+/// The `rank`th largest distinct joltage from exactly `k` batteries in a bank, counting the
+/// largest as 1, turning on the earliest batteries that give it. `None` if there are fewer
+/// distinct joltages than that.
+pub fn nth_largest_selection(bank: &[u64], k: usize, rank: u128) -> Option<Selection> {
+    let n = bank.len();
+    if rank == 0 || n < k {
+        return None;
+    }
+    // next[i][d] is where the first d at or after the ith battery is
+    let mut next = vec![[None; 10]; n + 1];
+    for i in (0..n).rev() {
+        next[i] = next[i + 1];
+        next[i][bank[i] as usize] = Some(i);
+    }
+    // distinct[i][r] is how many different r digit joltages the batteries from i onwards give.
+    // Each one starts with some digit, and taking the first of that digit leaves the most
+    // choices for the rest. There can be more than fit in a u128, but rank never is.
+    let mut distinct = vec![vec![0u128; k + 1]; n + 1];
+    for i in (0..=n).rev() {
+        distinct[i][0] = 1;
+        for r in 1..=k {
+            distinct[i][r] = next[i].iter().flatten().fold(0u128, |total, p| {
+                total.saturating_add(distinct[p + 1][r - 1])
+            });
+        }
+    }
+    let mut rank = rank;
+    let mut i = 0;
+    let mut indices = vec![];
+    for r in (1..=k).rev() {
+        let mut chosen = None;
+        for p in next[i].iter().rev().flatten() {
+            let count = distinct[p + 1][r - 1];
+            if rank <= count {
+                chosen = Some(*p);
+                break;
+            }
+            rank -= count;
+        }
+        let p = chosen?;
+        indices.push(p);
+        i = p + 1;
+    }
+    (rank == 1).then(|| Selection::new(bank, indices))
+}
+
+/// How many batteries to turn on in each bank, and how many threads to share the banks out to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Config {
+    pub part1: usize,
+    pub part2: usize,
+    pub threads: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            part1: 2,
+            part2: 12,
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Config = Config;
+    type Input = Banks;
+    type Answer1 = Joltage;
+    type Answer2 = Joltage;
+
+    fn parse(input: &str, config: &Config) -> Result<Self::Input, ParseError> {
+        parse_banks(input, config.part1.max(config.part2), config.threads)
+    }
+
+    // Parsing made sure every bank has enough batteries for both parts
+    fn part1(banks: &Banks, config: &Config) -> Joltage {
+        total_joltage(banks, config.part1, config.threads).unwrap()
+    }
+
+    fn part2(banks: &Banks, config: &Config) -> Joltage {
+        total_joltage(banks, config.part2, config.threads).unwrap()
+    }
+
+    fn examples() -> Vec<Example<Self::Config>> {
+        vec![Example {
+            name: "basic",
+            input: BASIC_INPUT,
+            config: Config::default(),
+            parts: &[1, 2],
+        }]
+    }
+}
+
+pub fn part1(input: &str) -> Joltage {
+    Day03::solve_part1(input, &Config::default()).unwrap()
+}
+
+pub fn part2(input: &str) -> Joltage {
+    Day03::solve_part2(input, &Config::default()).unwrap()
+}
+
+/// The batteries turned on in each bank, for `k` batteries per bank.
+pub fn selections(input: &str, k: usize) -> Vec<Selection> {
+    parse_input(input, k)
+        .unwrap()
+        .iter()
+        .map(|bank| largest_selection(bank, k).unwrap())
+        .collect()
+}
+
+pub const BASIC_INPUT: &str = "987654321111111
+811111111111119
+234234234234278
+818181911112111";
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BASIC_INPUT, Block, Constraints, Day03, TooFewBatteries, constrained_selection,
+        largest_joltage, largest_selection, nth_largest_selection, parse_input, part1, part2,
+        selections, smallest_selection,
+    };
+    use common::Solution;
+    use std::collections::BTreeSet;
+
+    fn joltage(bank: &[u64], k: usize) -> u128 {
+        largest_joltage(bank, k).unwrap().value().unwrap()
+    }
+
+    #[test]
+    fn largest_two_digits() {
+        assert_eq!(joltage(&[9, 8, 7, 6, 5, 4, 3, 2, 1, 1], 2), 98);
+        assert_eq!(joltage(&[8, 1, 1, 1, 1, 9], 2), 89);
+        assert_eq!(joltage(&[2, 3, 4, 2, 3, 4, 2, 7, 8], 2), 78);
+        assert_eq!(joltage(&[8, 1, 8, 1, 9, 1, 1, 2, 1], 2), 92);
+    }
+
+    #[test]
+    fn basic_test_part1() {
+        assert_eq!(part1(BASIC_INPUT).value(), Some(357))
+    }
+
+    #[test]
+    fn largest_twelve_digits() {
+        assert_eq!(
+            joltage(&[9, 8, 7, 6, 5, 4, 3, 2, 1, 1, 1, 1, 1, 1, 1], 12),
+            987654321111
+        );
+        assert_eq!(
+            joltage(&[8, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 9], 12),
+            811111111119
+        );
+        assert_eq!(
+            joltage(&[2, 3, 4, 2, 3, 4, 2, 3, 4, 2, 3, 4, 2, 7, 8], 12),
+            434234234278
+        );
+        assert_eq!(
+            joltage(&[8, 1, 8, 1, 8, 1, 9, 1, 1, 1, 1, 2, 1, 1, 1], 12),
+            888911112111
+        );
+    }
+
+    #[test]
+    fn basic_test_part2() {
+        assert_eq!(part2(BASIC_INPUT).value(), Some(3121910778619))
+    }
+
+    #[test]
+    fn any_number_of_batteries() {
+        let bank: Vec<u64> = (0..50).map(|i| (i * 7 % 10) as u64).collect();
+        assert_eq!(joltage(&bank, 0), 0);
+        assert_eq!(
+            largest_joltage(&bank, 50).unwrap().to_string(),
+            "07418529630741852963074185296307418529630741852963"
+        );
+        let big = largest_joltage(&bank, 40).unwrap();
+        assert_eq!(big.to_string(), "9741852963074185296307418529630741852963");
+        assert_eq!(big.value(), None);
+        assert_eq!(joltage(&bank, 20), 99978529630741852963);
+        assert_eq!(
+            largest_joltage(&[1, 2], 3),
+            Err(TooFewBatteries {
+                batteries: 2,
+                wanted: 3
+            })
+        );
+        let err = Day03::parse("123456789012\n123", &Default::default()).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(err.expected, "at least 12 batteries");
+    }
+
+    #[test]
+    fn selection_shows_batteries() {
+        let selection = &selections(BASIC_INPUT, 12)[3];
+        assert_eq!(
+            selection.indices,
+            vec![0, 2, 4, 6, 7, 8, 9, 10, 11, 12, 13, 14]
+        );
+        assert_eq!(selection.joltage.value(), Some(888911112111));
+        assert_eq!(
+            selection.to_string(),
+            "888911112111 from batteries 0,2,4,6,7,8,9,10,11,12,13,14
+818181911112111
+^ ^ ^ ^^^^^^^^^
+"
+        );
+        let banks = parse_input(BASIC_INPUT, 12).unwrap();
+        let selection = largest_selection(&banks[1], 2).unwrap();
+        assert_eq!(selection.highlighted, "811111111111119\n^             ^");
+    }
+
+    // Every way to turn on k batteries, to check the constrained selection against
+    fn brute_force(bank: &[u64], k: usize, constraints: &Constraints) -> Option<Vec<u64>> {
+        (0u32..1 << bank.len())
+            .filter(|mask| mask.count_ones() as usize == k)
+            .map(|mask| {
+                (0..bank.len())
+                    .filter(|i| mask >> i & 1 == 1)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|on| {
+                on.windows(2).all(|w| w[1] - w[0] >= constraints.min_gap)
+                    && on.iter().all(|i| !constraints.forbidden.contains(i))
+                    && constraints.block.is_none_or(|b| {
+                        (0..bank.len()).step_by(b.size).all(|start| {
+                            on.iter()
+                                .filter(|i| (start..start + b.size).contains(i))
+                                .count()
+                                <= b.max
+                        })
+                    })
+            })
+            .map(|on| on.iter().map(|i| bank[*i]).collect())
+            .max()
+    }
+
+    #[test]
+    fn constrained_selection_matches_brute_force() {
+        let bank = [8, 1, 8, 1, 9, 1, 1, 2, 7, 9, 3, 3];
+        let mut all = vec![Constraints::default()];
+        for min_gap in 1..4 {
+            for forbidden in [vec![], vec![4], vec![0, 9, 11]] {
+                for block in [None, Some((3, 1)), Some((4, 2)), Some((5, 3))] {
+                    all.push(Constraints {
+                        min_gap,
+                        forbidden: forbidden.clone(),
+                        block: block.map(|(size, max)| Block { size, max }),
+                    });
+                }
+            }
+        }
+        for constraints in all.iter() {
+            for k in 0..=bank.len() {
+                let selection = constrained_selection(&bank, k, constraints);
+                assert_eq!(
+                    selection.map(|s| s.joltage.digits),
+                    brute_force(&bank, k, constraints),
+                    "{} batteries with {:?}",
+                    k,
+                    constraints
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn unconstrained_matches_largest() {
+        let banks = parse_input(BASIC_INPUT, 12).unwrap();
+        for bank in banks.iter() {
+            for k in [2, 12] {
+                assert_eq!(
+                    constrained_selection(bank, k, &Constraints::default()),
+                    Some(largest_selection(bank, k).unwrap())
+                );
+            }
+        }
+        let spaced = Constraints {
+            min_gap: 2,
+            forbidden: vec![0],
+            ..Constraints::default()
+        };
+        let selection = constrained_selection(&banks[3], 6, &spaced).unwrap();
+        assert_eq!(selection.indices, vec![2, 6, 8, 10, 12, 14]);
+        assert_eq!(constrained_selection(&banks[3], 8, &spaced), None);
+    }
+
+    // Every different k digit joltage from a bank, largest first
+    fn all_joltages(bank: &[u64], k: usize) -> Vec<Vec<u64>> {
+        let mut joltages = BTreeSet::new();
+        for mask in (0u32..1 << bank.len()).filter(|m| m.count_ones() as usize == k) {
+            let digits: Vec<u64> = (0..bank.len())
+                .filter(|i| mask >> i & 1 == 1)
+                .map(|i| bank[i])
+                .collect();
+            joltages.insert(digits);
+        }
+        joltages.into_iter().rev().collect()
+    }
+
+    #[test]
+    fn smallest_joltages() {
+        let banks = parse_input(BASIC_INPUT, 12).unwrap();
+        let smallest: Vec<u128> = banks
+            .iter()
+            .map(|bank| {
+                smallest_selection(bank, 3, false)
+                    .unwrap()
+                    .joltage
+                    .value()
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(smallest, vec![111, 111, 222, 111]);
+        for bank in banks.iter() {
+            for k in 0..=4 {
+                let smallest = smallest_selection(bank, k, false).unwrap();
+                assert_eq!(Some(&smallest.joltage.digits), all_joltages(bank, k).last());
+            }
+        }
+        let bank = [3, 0, 1, 0, 2, 0];
+        let digits = |k, leading_zero| {
+            smallest_selection(&bank, k, leading_zero).map(|s| s.joltage.to_string())
+        };
+        assert_eq!(digits(3, true), Some("000".to_string()));
+        assert_eq!(digits(3, false), Some("100".to_string()));
+        assert_eq!(digits(6, false), Some("301020".to_string()));
+        assert_eq!(smallest_selection(&[0, 0, 5], 3, false), None);
+        assert_eq!(smallest_selection(&[1, 2], 3, true), None);
+    }
+
+    #[test]
+    fn nth_largest_joltages() {
+        let banks = parse_input(BASIC_INPUT, 12).unwrap();
+        for bank in banks.iter() {
+            for k in 1..=3 {
+                let all = all_joltages(bank, k);
+                for (rank, digits) in all.iter().enumerate() {
+                    let selection = nth_largest_selection(bank, k, rank as u128 + 1).unwrap();
+                    assert_eq!(&selection.joltage.digits, digits);
+                }
+                assert_eq!(nth_largest_selection(bank, k, all.len() as u128 + 1), None);
+            }
+            assert_eq!(
+                nth_largest_selection(bank, 12, 1),
+                Some(largest_selection(bank, 12).unwrap())
+            );
+        }
+        let second = nth_largest_selection(&banks[0], 12, 2).unwrap();
+        assert_eq!(second.joltage.to_string(), "987654311111");
+        assert_eq!(nth_largest_selection(&banks[0], 12, 0), None);
+    }
+}
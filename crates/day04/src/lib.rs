@@ -0,0 +1,148 @@
+use common::{Example, ParseError, Solution, lines};
+
+fn parse_input(input: &str) -> Result<Vec<Vec<bool>>, ParseError> {
+    let mut rows: Vec<Vec<bool>> = vec![];
+    for line in lines(input) {
+        let mut row = vec![];
+        for (i, c) in line.text.char_indices() {
+            match c {
+                '.' => row.push(false),
+                '@' => row.push(true),
+                _ => return Err(line.error(&line.text[i..i + c.len_utf8()], "`.` or `@`")),
+            }
+        }
+        if let Some(first) = rows.first()
+            && first.len() != row.len()
+        {
+            return Err(line.end_error(format!("a row {} wide", first.len())));
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn is_blocked(x: isize, y: isize, buf: &[Vec<bool>]) -> bool {
+    if x < 0 || y < 0 || x >= buf[0].len() as isize || y >= buf.len() as isize {
+        false
+    } else {
+        buf[y as usize][x as usize]
+    }
+}
+
+fn count_empty(x: isize, y: isize, buf: &[Vec<bool>]) -> usize {
+    let mut count = 0;
+    if !is_blocked(x - 1, y - 1, buf) {
+        count += 1;
+    }
+    if !is_blocked(x, y - 1, buf) {
+        count += 1;
+    }
+    if !is_blocked(x + 1, y - 1, buf) {
+        count += 1;
+    }
+    if !is_blocked(x - 1, y, buf) {
+        count += 1;
+    }
+    if !is_blocked(x + 1, y, buf) {
+        count += 1;
+    }
+    if !is_blocked(x - 1, y + 1, buf) {
+        count += 1;
+    }
+    if !is_blocked(x, y + 1, buf) {
+        count += 1;
+    }
+    if !is_blocked(x + 1, y + 1, buf) {
+        count += 1;
+    }
+    count
+}
+
+fn step(buf: &[Vec<bool>]) -> (Vec<Vec<bool>>, u64) {
+    let mut count = 0;
+    let mut next = buf.to_vec();
+    for y in 0..buf.len() {
+        for x in 0..buf[0].len() {
+            if buf[y][x] && count_empty(x as isize, y as isize, buf) >= 5 {
+                next[y][x] = false;
+                count += 1;
+            }
+        }
+    }
+    (next, count)
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Config = ();
+    type Input = Vec<Vec<bool>>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str, _config: &()) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(buf: &Self::Input, _config: &()) -> u64 {
+        let (_, count) = step(buf);
+        count
+    }
+
+    fn part2(buf: &Self::Input, _config: &()) -> u64 {
+        let mut buf = buf.clone();
+        let mut count = 0;
+        loop {
+            let (next, removed) = step(&buf);
+            if removed == 0 {
+                break;
+            }
+            count += removed;
+            buf = next;
+        }
+        count
+    }
+
+    fn examples() -> Vec<Example<Self::Config>> {
+        vec![Example {
+            name: "basic",
+            input: BASIC_INPUT,
+            config: (),
+            parts: &[1, 2],
+        }]
+    }
+}
+
+pub fn part1(input: &str) -> u64 {
+    Day04::solve_part1(input, &()).unwrap()
+}
+
+pub fn part2(input: &str) -> u64 {
+    Day04::solve_part2(input, &()).unwrap()
+}
+
+pub const BASIC_INPUT: &str = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.";
+
+#[cfg(test)]
+mod tests {
+    use crate::{BASIC_INPUT, part1, part2};
+
+    #[test]
+    fn basic_test_part1() {
+        assert_eq!(part1(BASIC_INPUT), 13)
+    }
+
+    #[test]
+    fn basic_test_part2() {
+        assert_eq!(part2(BASIC_INPUT), 43)
+    }
+}
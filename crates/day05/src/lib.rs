@@ -0,0 +1,134 @@
+use common::{Example, Line, ParseError, Solution, lines};
+use std::ops::RangeInclusive;
+
+pub struct Input {
+    ranges: Vec<RangeInclusive<u64>>,
+    to_check: Vec<u64>,
+}
+
+fn parse_range(line: &Line) -> Result<RangeInclusive<u64>, ParseError> {
+    let (low, high) = line.split_once(line.text, "-")?;
+    let low = line.parse::<u64>(low, "the low end of a range")?;
+    let high = line.parse::<u64>(high, "the high end of a range")?;
+    Ok(low..=high)
+}
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let mut ranges = vec![];
+    let mut to_check = vec![];
+    let mut done_ranges = false;
+    for line in lines(input) {
+        if line.text.trim().is_empty() {
+            done_ranges = true;
+        } else if !done_ranges {
+            ranges.push(parse_range(&line)?);
+        } else {
+            to_check.push(line.parse::<u64>(line.text, "an ingredient ID")?);
+        }
+    }
+    if ranges.is_empty() {
+        return Err(ParseError::end_of_input(input, "a range"));
+    }
+    Ok(Input { ranges, to_check })
+}
+
+fn range_intersects(left: &RangeInclusive<u64>, right: &RangeInclusive<u64>) -> bool {
+    left.contains(right.start())
+        || left.contains(right.end())
+        || right.contains(left.start())
+        || right.contains(left.end())
+}
+
+fn merge(left: &RangeInclusive<u64>, right: &RangeInclusive<u64>) -> RangeInclusive<u64> {
+    (*left.start()).min(*right.start())..=(*left.end()).max(*right.end())
+}
+
+fn merge_ranges(ranges: &mut [RangeInclusive<u64>]) -> Vec<RangeInclusive<u64>> {
+    ranges.sort_by(|left, right| left.start().cmp(right.start()));
+    let mut new_ranges = vec![ranges[0].clone()];
+    for to_consider in ranges.iter().skip(1) {
+        let last_idx = new_ranges.len() - 1;
+        if range_intersects(&new_ranges[last_idx], to_consider) {
+            new_ranges[last_idx] = merge(to_consider, &new_ranges[last_idx]);
+        } else {
+            new_ranges.push(to_consider.clone());
+        }
+    }
+    new_ranges
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Config = ();
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str, _config: &()) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input, _config: &()) -> u64 {
+        let mut count = 0;
+        for check in input.to_check.iter() {
+            for range in input.ranges.iter() {
+                if range.contains(check) {
+                    count += 1;
+                    break;
+                }
+            }
+        }
+        count
+    }
+
+    fn part2(input: &Self::Input, _config: &()) -> u64 {
+        let mut ranges = input.ranges.clone();
+        let merged = merge_ranges(&mut ranges);
+        merged.into_iter().map(|r| r.end() - r.start() + 1).sum()
+    }
+
+    fn examples() -> Vec<Example<Self::Config>> {
+        vec![Example {
+            name: "basic",
+            input: BASIC_INPUT,
+            config: (),
+            parts: &[1, 2],
+        }]
+    }
+}
+
+pub fn part1(input: &str) -> u64 {
+    Day05::solve_part1(input, &()).unwrap()
+}
+
+pub fn part2(input: &str) -> u64 {
+    Day05::solve_part2(input, &()).unwrap()
+}
+
+pub const BASIC_INPUT: &str = "3-5
+10-14
+16-20
+12-18
+
+1
+5
+8
+11
+17
+32";
+
+#[cfg(test)]
+mod tests {
+    use crate::{BASIC_INPUT, part1, part2};
+
+    #[test]
+    fn basic_test_part1() {
+        assert_eq!(part1(BASIC_INPUT), 3)
+    }
+
+    #[test]
+    fn basic_test_part2() {
+        assert_eq!(part2(BASIC_INPUT), 14)
+    }
+}
@@ -0,0 +1,263 @@
+use common::{Example, Line, ParseError, Solution, lines};
+use std::str::FromStr;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+enum Operator {
+    Add,
+    Mult,
+}
+
+impl Operator {
+    fn add(acc: u64, operand: u64) -> u64 {
+        acc + operand
+    }
+
+    fn mult(acc: u64, operand: u64) -> u64 {
+        acc * operand
+    }
+
+    fn fold_fn(&self) -> impl Fn(u64, u64) -> u64 {
+        match self {
+            Self::Add => Self::add,
+            Self::Mult => Self::mult,
+        }
+    }
+
+    fn acc_init(&self) -> u64 {
+        match self {
+            Self::Add => 0,
+            Self::Mult => 1,
+        }
+    }
+}
+
+impl FromStr for Operator {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "+" => Ok(Operator::Add),
+            "*" => Ok(Operator::Mult),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Input {
+    nums: Vec<Vec<u64>>,
+    ops: Vec<Operator>,
+}
+
+// Splits the worksheet into its rows of numbers and the row of operators at the bottom
+fn split_rows(input: &str) -> Result<(Vec<Line<'_>>, Line<'_>), ParseError> {
+    let mut rows: Vec<Line> = lines(input).collect();
+    let ops_line = rows
+        .pop()
+        .ok_or_else(|| ParseError::end_of_input(input, "a row of operators"))?;
+    Ok((rows, ops_line))
+}
+
+fn parse_ops(line: &Line) -> Result<Vec<Operator>, ParseError> {
+    line.text
+        .split_whitespace()
+        .map(|s| {
+            s.parse::<Operator>()
+                .map_err(|_| line.error(s, "`+` or `*`"))
+        })
+        .collect()
+}
+
+fn parse_input_part1(input: &str) -> Result<Input, ParseError> {
+    let (rows, ops_line) = split_rows(input)?;
+    let ops = parse_ops(&ops_line)?;
+    let mut nums: Vec<Vec<u64>> = Vec::with_capacity(rows.len());
+    for line in rows {
+        let row = line
+            .text
+            .split_whitespace()
+            .map(|num| line.parse::<u64>(num, "a number"))
+            .collect::<Result<Vec<u64>, ParseError>>()?;
+        if row.len() != ops.len() {
+            return Err(line.end_error(format!("{} numbers", ops.len())));
+        }
+        nums.push(row);
+    }
+    Ok(Input { nums, ops })
+}
+
+struct Part2Problem {
+    nums: Vec<u64>,
+    op: Operator,
+}
+
+fn parse_digits(digits: &[u8]) -> Option<u64> {
+    if digits.iter().all(|d| *d == b' ') {
+        None
+    } else {
+        Some(
+            digits
+                .iter()
+                .filter(|d| **d != b' ')
+                .fold(0, |acc, d| acc * 10 + (d - b'0') as u64),
+        )
+    }
+}
+
+fn get_numbers(rows: &[Line]) -> Result<Vec<Option<u64>>, ParseError> {
+    for line in rows {
+        if let Some((i, c)) = line
+            .text
+            .char_indices()
+            .find(|(_, c)| !c.is_ascii_digit() && *c != ' ')
+        {
+            return Err(line.error(&line.text[i..i + c.len_utf8()], "a digit or space"));
+        }
+    }
+    // Rows can be ragged if trailing spaces were trimmed, so missing columns count as spaces
+    let width = rows.iter().map(|line| line.text.len()).max().unwrap_or(0);
+    let mut numbers: Vec<Vec<u8>> = vec![];
+    for col in 0..width {
+        let cur_num = rows
+            .iter()
+            .map(|line| line.text.as_bytes().get(col).copied().unwrap_or(b' '))
+            .collect();
+        numbers.push(cur_num);
+    }
+    Ok(numbers
+        .into_iter()
+        .map(|digits| parse_digits(&digits))
+        .collect())
+}
+
+fn parse_input_part2(input: &str) -> Result<Vec<Part2Problem>, ParseError> {
+    let (rows, ops_line) = split_rows(input)?;
+    let numbers = get_numbers(&rows)?;
+    let ops = parse_ops(&ops_line)?;
+    let op_for = |op_idx: usize| {
+        ops.get(op_idx)
+            .copied()
+            .ok_or_else(|| ops_line.end_error("an operator for every problem"))
+    };
+    let mut cur_numbers = vec![];
+    let mut result = vec![];
+    let mut op_idx = 0;
+    for number in numbers {
+        if let Some(num) = number {
+            cur_numbers.push(num);
+        } else {
+            result.push(Part2Problem {
+                nums: cur_numbers,
+                op: op_for(op_idx)?,
+            });
+            cur_numbers = vec![];
+            op_idx += 1;
+        }
+    }
+    result.push(Part2Problem {
+        nums: cur_numbers,
+        op: op_for(op_idx)?,
+    });
+    Ok(result)
+}
+
+pub struct Worksheet {
+    rows: Input,
+    columns: Vec<Part2Problem>,
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Config = ();
+    type Input = Worksheet;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str, _config: &()) -> Result<Self::Input, ParseError> {
+        Ok(Worksheet {
+            rows: parse_input_part1(input)?,
+            columns: parse_input_part2(input)?,
+        })
+    }
+
+    fn part1(worksheet: &Self::Input, _config: &()) -> u64 {
+        let input = &worksheet.rows;
+        let mut total = 0;
+        for (col, op) in input.ops.iter().enumerate() {
+            let fold_fn = op.fold_fn();
+            let start_val = op.acc_init();
+            let col_result = input
+                .nums
+                .iter()
+                .map(|row| row[col])
+                .fold(start_val, fold_fn);
+            total += col_result;
+        }
+        total
+    }
+
+    fn part2(worksheet: &Self::Input, _config: &()) -> u64 {
+        worksheet
+            .columns
+            .iter()
+            .map(|prob| {
+                prob.nums
+                    .iter()
+                    .copied()
+                    .fold(prob.op.acc_init(), prob.op.fold_fn())
+            })
+            .sum()
+    }
+
+    fn examples() -> Vec<Example<Self::Config>> {
+        vec![
+            Example {
+                name: "basic",
+                input: BASIC_INPUT,
+                config: (),
+                parts: &[1, 2],
+            },
+            Example {
+                name: "derived",
+                input: DERIVED_INPUT,
+                config: (),
+                parts: &[1],
+            },
+        ]
+    }
+}
+
+pub fn part1(input: &str) -> u64 {
+    Day06::solve_part1(input, &()).unwrap()
+}
+
+pub fn part2(input: &str) -> u64 {
+    Day06::solve_part2(input, &()).unwrap()
+}
+
+pub const BASIC_INPUT: &str = "123 328  51 64 
+ 45 64  387 23 
+  6 98  215 314
+*   +   *   +  ";
+
+pub const DERIVED_INPUT: &str = "  6  8 99  
+187 49 5422
+635 82 2573
+552 33 5463
+*   *  +   ";
+
+#[cfg(test)]
+mod tests {
+    use crate::{BASIC_INPUT, DERIVED_INPUT, part1, part2};
+
+    #[test]
+    fn basic_test_part1() {
+        assert_eq!(part1(BASIC_INPUT), 4277556);
+        assert_eq!(part1(DERIVED_INPUT), 394357749);
+    }
+
+    #[test]
+    fn basic_test_part2() {
+        assert_eq!(part2(BASIC_INPUT), 3263827);
+    }
+}
@@ -0,0 +1,216 @@
+use common::{Example, ParseError, Solution, lines};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+struct Location {
+    y: usize,
+    x: usize,
+}
+
+#[derive(PartialEq, Eq)]
+pub struct Input {
+    start: Location,
+    splitters: HashSet<Location>,
+    width: usize,
+    height: usize,
+}
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let mut splitters: HashSet<Location> = HashSet::new();
+    let mut start = None;
+    for line in lines(input) {
+        let y = line.number - 1;
+        for (x, c) in line.text.char_indices() {
+            match c {
+                '.' => {}
+                '^' => {
+                    splitters.insert(Location { y, x });
+                }
+                'S' if start.is_none() => start = Some(Location { y, x }),
+                _ => return Err(line.error(&line.text[x..x + c.len_utf8()], "`.` or `^`")),
+            }
+        }
+    }
+    let start = start.ok_or_else(|| ParseError::end_of_input(input, "a start position `S`"))?;
+    let width = input.lines().next().unwrap().len();
+    let height = input.lines().count();
+    Ok(Input {
+        start,
+        splitters,
+        width,
+        height,
+    })
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Config = ();
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = usize;
+
+    fn parse(input: &str, _config: &()) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input, _config: &()) -> u64 {
+        let max_y = input.height;
+        let mut queue = VecDeque::new();
+        queue.push_back(input.start);
+        let mut considered: HashSet<Location> = HashSet::new();
+        let mut split_count = 0;
+        while let Some(pos) = queue.pop_front() {
+            if considered.contains(&pos) {
+                continue;
+            }
+            considered.insert(pos);
+            if input.splitters.contains(&pos) {
+                split_count += 1;
+                queue.push_back(Location {
+                    y: pos.y,
+                    x: pos.x - 1,
+                });
+                queue.push_back(Location {
+                    y: pos.y,
+                    x: pos.x + 1,
+                });
+            } else if pos.y < max_y {
+                queue.push_back(Location {
+                    y: pos.y + 1,
+                    x: pos.x,
+                });
+            }
+        }
+        split_count
+    }
+
+    fn part2(input: &Self::Input, _config: &()) -> usize {
+        let max_y = input.height;
+        let max_x = input.width;
+        let mut next_queue = VecDeque::new();
+        next_queue.push_back(input.start);
+        let mut times_hit: HashMap<Location, usize> = HashMap::new();
+        times_hit.insert(input.start, 1);
+        while !next_queue.is_empty() {
+            let mut queue = next_queue;
+            next_queue = VecDeque::new();
+            while let Some(pos) = queue.pop_front() {
+                let current_hits = times_hit.get(&pos).copied().unwrap();
+                if input.splitters.contains(&pos) {
+                    let left = Location {
+                        y: pos.y,
+                        x: pos.x - 1,
+                    };
+                    let left_down = Location {
+                        y: pos.y + 1,
+                        x: pos.x - 1,
+                    };
+                    let right = Location {
+                        y: pos.y,
+                        x: pos.x + 1,
+                    };
+                    if let Some(count) = times_hit.get_mut(&right) {
+                        *count += current_hits;
+                    } else {
+                        times_hit.insert(right, current_hits);
+                        queue.push_front(right);
+                    }
+                    if let Some(count) = times_hit.get_mut(&left) {
+                        *count += current_hits;
+                        if let Some(count) = times_hit.get_mut(&left_down) {
+                            *count += current_hits;
+                        }
+                    } else {
+                        times_hit.insert(left, current_hits);
+                        queue.push_front(left);
+                    }
+                } else if pos.y < max_y {
+                    let next = Location {
+                        y: pos.y + 1,
+                        x: pos.x,
+                    };
+                    if let Some(count) = times_hit.get_mut(&next) {
+                        *count += current_hits;
+                    } else {
+                        times_hit.insert(next, current_hits);
+                        next_queue.push_back(next);
+                    }
+                }
+            }
+        }
+        let mut timeline_count = 0;
+        for x in 0..max_x {
+            let loc = Location { y: max_y, x };
+            if let Some(timelines) = times_hit.get(&loc) {
+                timeline_count += timelines;
+            }
+        }
+        timeline_count
+    }
+
+    fn examples() -> Vec<Example<Self::Config>> {
+        vec![
+            Example {
+                name: "basic",
+                input: BASIC_INPUT,
+                config: (),
+                parts: &[1, 2],
+            },
+            Example {
+                name: "simple",
+                input: SIMPLE_INPUT,
+                config: (),
+                parts: &[2],
+            },
+        ]
+    }
+}
+
+pub fn part1(input: &str) -> u64 {
+    Day07::solve_part1(input, &()).unwrap()
+}
+
+pub fn part2(input: &str) -> usize {
+    Day07::solve_part2(input, &()).unwrap()
+}
+
+pub const BASIC_INPUT: &str = ".......S.......
+...............
+.......^.......
+...............
+......^.^......
+...............
+.....^.^.^.....
+...............
+....^.^...^....
+...............
+...^.^...^.^...
+...............
+..^...^.....^..
+...............
+.^.^.^.^.^...^.
+...............";
+
+pub const SIMPLE_INPUT: &str = "..S..
+.....
+..^..
+.....
+.^.^.
+.....";
+
+#[cfg(test)]
+mod tests {
+    use crate::{BASIC_INPUT, SIMPLE_INPUT, part1, part2};
+
+    #[test]
+    fn basic_test_part1() {
+        assert_eq!(part1(BASIC_INPUT), 21);
+    }
+
+    #[test]
+    fn basic_test_part2() {
+        assert_eq!(part2(SIMPLE_INPUT), 4);
+        assert_eq!(part2(BASIC_INPUT), 40);
+    }
+}
@@ -0,0 +1,290 @@
+use common::{Example, Line, ParseError, Solution, lines};
+use std::collections::HashMap;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub struct Coord {
+    x: i64,
+    y: i64,
+    z: i64,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, Hash)]
+struct PairWithDistance {
+    distance: i64,
+    a: usize,
+    b: usize,
+}
+
+// Disjoint set union over junction box indices, with each root knowing its circuit's size
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Circuits {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl Circuits {
+    fn new(boxes: usize) -> Self {
+        Circuits {
+            parent: (0..boxes).collect(),
+            size: vec![1; boxes],
+            count: boxes,
+        }
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    // Returns false if they were already in the same circuit
+    fn connect(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            (a, b) = (b, a);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.count -= 1;
+        true
+    }
+
+    fn sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.parent.len())
+            .filter(|i| self.parent[*i] == *i)
+            .map(|i| self.size[i])
+    }
+}
+
+fn distance_squared(a: &Coord, b: &Coord) -> i64 {
+    let xdiff = b.x - a.x;
+    let ydiff = b.y - a.y;
+    let zdiff = b.z - a.z;
+    xdiff * xdiff + ydiff * ydiff + zdiff * zdiff
+}
+
+fn parse_coord(line: &Line) -> Result<Coord, ParseError> {
+    let (x, rest) = line.split_once(line.text, ",")?;
+    let (y, z) = line.split_once(rest, ",")?;
+    Ok(Coord {
+        x: line.parse(x, "an x coordinate")?,
+        y: line.parse(y, "a y coordinate")?,
+        z: line.parse(z, "a z coordinate")?,
+    })
+}
+
+fn parse_input(input: &str) -> Result<Vec<Coord>, ParseError> {
+    lines(input).map(|line| parse_coord(&line)).collect()
+}
+
+type Cell = (i64, i64, i64);
+
+// Yields every pair of junction boxes, closest first, without computing them all up front.
+// Pairs come in batches out to a radius, found by bucketing the boxes into a grid of
+// radius-sized cells so only neighbouring cells need checking, and the radius doubles each batch.
+struct ClosePairs<'a> {
+    coords: &'a [Coord],
+    radius: i64,
+    // Everything at or below this squared distance has already been yielded
+    done: i64,
+    max_distance: i64,
+    batch: Vec<PairWithDistance>,
+}
+
+impl<'a> ClosePairs<'a> {
+    fn new(coords: &'a [Coord]) -> Self {
+        let extent = |axis: fn(&Coord) -> i64| {
+            let min = coords.iter().map(axis).min().unwrap_or(0);
+            let max = coords.iter().map(axis).max().unwrap_or(0);
+            max - min
+        };
+        let (dx, dy, dz) = (extent(|c| c.x), extent(|c| c.y), extent(|c| c.z));
+        // Start around the typical spacing between boxes, so the first batch is small
+        let volume = (dx + 1) as f64 * (dy + 1) as f64 * (dz + 1) as f64;
+        let radius = (volume / coords.len().max(1) as f64).cbrt().max(1.0) as i64;
+        ClosePairs {
+            coords,
+            radius,
+            done: -1,
+            max_distance: (dx as i128 * dx as i128
+                + dy as i128 * dy as i128
+                + dz as i128 * dz as i128)
+                .try_into()
+                .unwrap_or(i64::MAX),
+            batch: vec![],
+        }
+    }
+
+    fn cell(&self, coord: &Coord) -> Cell {
+        (
+            coord.x.div_euclid(self.radius),
+            coord.y.div_euclid(self.radius),
+            coord.z.div_euclid(self.radius),
+        )
+    }
+
+    fn fill_batch(&mut self) {
+        let limit = self.radius.saturating_mul(self.radius);
+        let mut grid: HashMap<Cell, Vec<usize>> = HashMap::new();
+        for (i, coord) in self.coords.iter().enumerate() {
+            grid.entry(self.cell(coord)).or_default().push(i);
+        }
+        for (a, coord) in self.coords.iter().enumerate() {
+            let (x, y, z) = self.cell(coord);
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for dz in -1..=1 {
+                        let Some(cell) = grid.get(&(x + dx, y + dy, z + dz)) else {
+                            continue;
+                        };
+                        for &b in cell {
+                            if b <= a {
+                                continue;
+                            }
+                            let distance = distance_squared(coord, &self.coords[b]);
+                            if distance > self.done && distance <= limit {
+                                self.batch.push(PairWithDistance { distance, a, b });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        // Reversed so the closest pair can be popped off the end
+        self.batch.sort_by(|p, q| q.cmp(p));
+        self.done = limit;
+        self.radius = self.radius.saturating_mul(2);
+    }
+}
+
+impl Iterator for ClosePairs<'_> {
+    type Item = PairWithDistance;
+
+    fn next(&mut self) -> Option<PairWithDistance> {
+        while self.batch.is_empty() {
+            if self.done >= self.max_distance {
+                return None;
+            }
+            self.fill_batch();
+        }
+        self.batch.pop()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Config {
+    pub connections: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { connections: 1000 }
+    }
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Config = Config;
+    type Input = Vec<Coord>;
+    type Answer1 = usize;
+    type Answer2 = i64;
+
+    fn parse(input: &str, _config: &Config) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(coords: &Self::Input, config: &Config) -> usize {
+        let mut circuits = Circuits::new(coords.len());
+        for pair in ClosePairs::new(coords).take(config.connections as usize) {
+            circuits.connect(pair.a, pair.b);
+        }
+        let mut sizes: Vec<usize> = circuits.sizes().collect();
+        sizes.sort();
+        sizes.iter().rev().take(3).product()
+    }
+
+    fn part2(coords: &Self::Input, _config: &Config) -> i64 {
+        let mut circuits = Circuits::new(coords.len());
+        for pair in ClosePairs::new(coords) {
+            if circuits.connect(pair.a, pair.b) && circuits.count == 1 {
+                return coords[pair.a].x * coords[pair.b].x;
+            }
+        }
+        panic!("Did not connect graph")
+    }
+
+    fn examples() -> Vec<Example<Config>> {
+        vec![Example {
+            name: "basic",
+            input: BASIC_INPUT,
+            config: Config { connections: 10 },
+            parts: &[1, 2],
+        }]
+    }
+}
+
+pub fn part1(input: &str, connections: u64) -> usize {
+    Day08::solve_part1(input, &Config { connections }).unwrap()
+}
+
+pub fn part2(input: &str) -> i64 {
+    Day08::solve_part2(input, &Config::default()).unwrap()
+}
+
+pub const BASIC_INPUT: &str = "162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689";
+
+#[cfg(test)]
+mod tests {
+    use crate::{BASIC_INPUT, ClosePairs, parse_input, part1, part2};
+
+    #[test]
+    fn basic_test_part1() {
+        assert_eq!(part1(BASIC_INPUT, 10), 40);
+    }
+
+    #[test]
+    fn close_pairs_come_in_order() {
+        let coords = parse_input(BASIC_INPUT).unwrap();
+        let pairs: Vec<i64> = ClosePairs::new(&coords).map(|p| p.distance).collect();
+        assert_eq!(pairs.len(), coords.len() * (coords.len() - 1) / 2);
+        assert!(pairs.is_sorted());
+    }
+
+    #[test]
+    fn basic_test_part2() {
+        assert_eq!(part2(BASIC_INPUT), 25272);
+    }
+
+    #[test]
+    fn widely_spread_boxes() {
+        let input = "0,0,0\n3000000,3000000,3000000\n1,1,1";
+        assert_eq!(part1(input, 10), 3);
+        assert_eq!(part2(input), 3000000);
+    }
+}
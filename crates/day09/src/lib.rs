@@ -0,0 +1,300 @@
+use common::{Example, Line, ParseError, Solution, lines};
+use std::collections::VecDeque;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub struct Coord {
+    x: usize,
+    y: usize,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+enum Tile {
+    Unknown,
+    Valid,
+    Invalid,
+}
+
+// The floor squashed down to just the rows and columns with red tiles in them, plus one for
+// each run of rows or columns between them. Every cell in a run is the same, so deciding
+// validity per cell is exact, and `invalid_sums` lets any rectangle be checked in O(1).
+#[derive(PartialEq, Eq, Debug, Clone)]
+struct CompressedGrid {
+    xs: Vec<usize>,
+    ys: Vec<usize>,
+    width: usize,
+    height: usize,
+    tiles: Vec<Tile>,
+    // invalid_sums[x + (width + 1) * y] counts the invalid floor tiles in cells left of x and
+    // above y
+    invalid_sums: Vec<usize>,
+}
+
+fn area(a: &Coord, b: &Coord) -> usize {
+    (a.x.max(b.x) - a.x.min(b.x) + 1) * (a.y.max(b.y) - a.y.min(b.y) + 1)
+}
+
+fn parse_coord(line: &Line) -> Result<Coord, ParseError> {
+    let (x, y) = line.split_once(line.text, ",")?;
+    let x = line.parse(x, "an x coordinate")?;
+    let y = line.parse(y, "a y coordinate")?;
+    Ok(Coord { x, y })
+}
+
+fn parse_input(input: &str) -> Result<Vec<Coord>, ParseError> {
+    let coords = lines(input)
+        .map(|line| parse_coord(&line))
+        .collect::<Result<Vec<Coord>, ParseError>>()?;
+    if coords.is_empty() {
+        return Err(ParseError::end_of_input(input, "a red tile"));
+    }
+    Ok(coords)
+}
+
+fn sorted_unique(values: impl Iterator<Item = usize>) -> Vec<usize> {
+    let mut values: Vec<usize> = values.collect();
+    values.sort();
+    values.dedup();
+    values
+}
+
+// Cell 2i + 1 is the i-th coordinate, and cell 2i is the gap before it
+fn cell(values: &[usize], value: usize) -> usize {
+    values.binary_search(&value).unwrap() * 2 + 1
+}
+
+// How many floor tiles wide a cell is. The outermost gaps are outside everything, so the
+// exact size doesn't matter as long as they count.
+fn cell_size(values: &[usize], cell: usize) -> usize {
+    if cell % 2 == 1 || cell == 0 || cell == values.len() * 2 {
+        1
+    } else {
+        values[cell / 2] - values[cell / 2 - 1] - 1
+    }
+}
+
+impl CompressedGrid {
+    fn new(coords: &[Coord]) -> Self {
+        let xs = sorted_unique(coords.iter().map(|c| c.x));
+        let ys = sorted_unique(coords.iter().map(|c| c.y));
+        let width = xs.len() * 2 + 1;
+        let height = ys.len() * 2 + 1;
+        let mut grid = CompressedGrid {
+            xs,
+            ys,
+            width,
+            height,
+            tiles: vec![Tile::Unknown; width * height],
+            invalid_sums: vec![],
+        };
+        grid.green_lines(coords);
+        grid.fill_outside();
+        grid.sum_invalid();
+        grid
+    }
+
+    fn cell_of(&self, coord: &Coord) -> Coord {
+        Coord {
+            x: cell(&self.xs, coord.x),
+            y: cell(&self.ys, coord.y),
+        }
+    }
+
+    fn green_lines(&mut self, coords: &[Coord]) {
+        for (i, a) in coords.iter().enumerate() {
+            let a = self.cell_of(a);
+            let b = self.cell_of(&coords[(i + 1) % coords.len()]);
+            for y in a.y.min(b.y)..=a.y.max(b.y) {
+                for x in a.x.min(b.x)..=a.x.max(b.x) {
+                    self.tiles[x + self.width * y] = Tile::Valid;
+                }
+            }
+        }
+    }
+
+    // Everything the outside can reach without crossing a line is invalid, and the rest is
+    // inside the loop
+    fn fill_outside(&mut self) {
+        let mut queue: VecDeque<Coord> = VecDeque::new();
+        queue.push_back(Coord { x: 0, y: 0 });
+        while let Some(coord) = queue.pop_front() {
+            let idx = coord.x + self.width * coord.y;
+            if self.tiles[idx] != Tile::Unknown {
+                continue;
+            }
+            self.tiles[idx] = Tile::Invalid;
+            if coord.x != 0 {
+                queue.push_back(Coord {
+                    x: coord.x - 1,
+                    y: coord.y,
+                });
+            }
+            if coord.y != 0 {
+                queue.push_back(Coord {
+                    x: coord.x,
+                    y: coord.y - 1,
+                });
+            }
+            if coord.x + 1 < self.width {
+                queue.push_back(Coord {
+                    x: coord.x + 1,
+                    y: coord.y,
+                });
+            }
+            if coord.y + 1 < self.height {
+                queue.push_back(Coord {
+                    x: coord.x,
+                    y: coord.y + 1,
+                });
+            }
+        }
+    }
+
+    fn sum_invalid(&mut self) {
+        let stride = self.width + 1;
+        self.invalid_sums = vec![0; stride * (self.height + 1)];
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let invalid = if self.tiles[x + self.width * y] == Tile::Invalid {
+                    cell_size(&self.xs, x) * cell_size(&self.ys, y)
+                } else {
+                    0
+                };
+                self.invalid_sums[x + 1 + stride * (y + 1)] = invalid
+                    + self.invalid_sums[x + stride * (y + 1)]
+                    + self.invalid_sums[x + 1 + stride * y]
+                    - self.invalid_sums[x + stride * y];
+            }
+        }
+    }
+
+    fn all_red_or_green(&self, a: &Coord, b: &Coord) -> bool {
+        let (a, b) = (self.cell_of(a), self.cell_of(b));
+        let stride = self.width + 1;
+        let (min_x, max_x) = (a.x.min(b.x), a.x.max(b.x) + 1);
+        let (min_y, max_y) = (a.y.min(b.y), a.y.max(b.y) + 1);
+        self.invalid_sums[max_x + stride * max_y] + self.invalid_sums[min_x + stride * min_y]
+            == self.invalid_sums[min_x + stride * max_y] + self.invalid_sums[max_x + stride * min_y]
+    }
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Config = ();
+    type Input = Vec<Coord>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str, _config: &()) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(coords: &Self::Input, _config: &()) -> usize {
+        let mut largest = 0;
+        for (i, a) in coords.iter().enumerate() {
+            for b in coords.iter().skip(i) {
+                let area = area(a, b);
+                if area > largest {
+                    largest = area;
+                }
+            }
+        }
+        largest
+    }
+
+    fn part2(coords: &Self::Input, _config: &()) -> usize {
+        let grid = CompressedGrid::new(coords);
+        let mut largest = 0;
+        for (i, a) in coords.iter().enumerate() {
+            for b in coords.iter().skip(i) {
+                let area = area(a, b);
+                if area > largest && grid.all_red_or_green(a, b) {
+                    largest = area;
+                }
+            }
+        }
+        largest
+    }
+
+    fn examples() -> Vec<Example<Self::Config>> {
+        vec![
+            Example {
+                name: "basic",
+                input: BASIC_INPUT,
+                config: (),
+                parts: &[1, 2],
+            },
+            Example {
+                name: "concave",
+                input: CONCAVE_INPUT,
+                config: (),
+                parts: &[2],
+            },
+        ]
+    }
+}
+
+pub fn part1(input: &str) -> usize {
+    Day09::solve_part1(input, &()).unwrap()
+}
+
+pub fn part2(input: &str) -> usize {
+    Day09::solve_part2(input, &()).unwrap()
+}
+
+pub const BASIC_INPUT: &str = "7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3";
+
+pub const CONCAVE_INPUT: &str = "7,1
+11,1
+11,7
+9,7
+9,5
+7,5
+7,4
+3,4
+3,5
+2,5
+2,3
+7,3";
+
+#[cfg(test)]
+mod tests {
+    use crate::{BASIC_INPUT, CONCAVE_INPUT, part1, part2};
+
+    /*
+    ..............
+    .......#XXX#..
+    .......XXXXX..
+    ..#XXXX#XXXX..
+    ..X#XXX#XXXX..
+    ..##...#X#XX..
+    .........XXX..
+    .........#X#..
+    ..............
+        */
+
+    #[test]
+    fn basic_test_part1() {
+        assert_eq!(part1(BASIC_INPUT), 50);
+    }
+
+    #[test]
+    fn adjacent_columns_leave_no_gap() {
+        // The notch between x = 2 and x = 3 is outside the loop but has no tiles in it
+        let input = "0,0\n2,0\n2,5\n3,5\n3,0\n5,0\n5,7\n0,7";
+        assert_eq!(part2(input), 48);
+    }
+
+    #[test]
+    fn basic_test_part2() {
+        assert_eq!(part2(BASIC_INPUT), 24);
+        assert_eq!(part2(CONCAVE_INPUT), 25);
+    }
+}
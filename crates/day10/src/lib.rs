@@ -0,0 +1,500 @@
+use common::{Example, Line, ParseError, Solution, lines};
+use std::str::FromStr;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone)]
+struct Button {
+    toggles_lights: Vec<usize>,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone)]
+pub struct Machine {
+    desired_lights: Vec<bool>,
+    buttons: Vec<Button>,
+    desired_joltages: Vec<u16>,
+}
+
+fn parse_button(line: &Line, tup: &str, light_count: usize) -> Result<Button, ParseError> {
+    let lst = tup
+        .strip_prefix('(')
+        .and_then(|tup| tup.strip_suffix(')'))
+        .ok_or_else(|| line.error(tup, "a button like `(0,2)`"))?;
+    let toggles_lights = lst
+        .split(',')
+        .map(|num| {
+            let light = line.parse::<usize>(num, "a light index")?;
+            if light >= light_count {
+                return Err(line.error(num, format!("a light index below {}", light_count)));
+            }
+            Ok(light)
+        })
+        .collect::<Result<Vec<usize>, ParseError>>()?;
+    Ok(Button { toggles_lights })
+}
+
+impl FromStr for Machine {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let line = Line { number: 1, text: s };
+        let desired_lights = s
+            .strip_prefix('[')
+            .ok_or_else(|| line.error(&s[..s.chars().next().map_or(0, char::len_utf8)], "`[`"))?;
+        let (desired_lights, buttons) = line.split_once(desired_lights, "]")?;
+        let desired_lights = desired_lights
+            .char_indices()
+            .map(|(i, c)| match c {
+                '.' => Ok(false),
+                '#' => Ok(true),
+                _ => Err(line.error(&desired_lights[i..i + c.len_utf8()], "`.` or `#`")),
+            })
+            .collect::<Result<Vec<bool>, ParseError>>()?;
+        let (buttons, desired_joltages) = line.split_once(buttons, "{")?;
+        // Buttons are bits in a u64 when solving for the lights
+        let buttons = buttons
+            .split_whitespace()
+            .enumerate()
+            .map(|(i, tup)| match i {
+                64.. => Err(line.error(tup, "at most 64 buttons")),
+                _ => parse_button(&line, tup, desired_lights.len()),
+            })
+            .collect::<Result<Vec<Button>, ParseError>>()?;
+        let desired_joltages = desired_joltages
+            .trim_end()
+            .strip_suffix('}')
+            .ok_or_else(|| line.end_error("`}`"))?;
+        let desired_joltages = desired_joltages
+            .split(',')
+            .map(|num| line.parse::<u16>(num, "a joltage"))
+            .collect::<Result<Vec<u16>, ParseError>>()?;
+        if desired_joltages.len() != desired_lights.len() {
+            return Err(line.end_error(format!("{} joltages", desired_lights.len())));
+        }
+        Ok(Machine {
+            desired_lights,
+            buttons,
+            desired_joltages,
+        })
+    }
+}
+
+fn compute_state(machine: &Machine, button_presses: &[usize]) -> Vec<bool> {
+    let mut state: Vec<bool> = vec![false; machine.desired_lights.len()];
+    for (press, count) in button_presses.iter().enumerate() {
+        if count % 2 == 1 {
+            for light in machine.buttons[press].toggles_lights.iter() {
+                state[*light] = !state[*light];
+            }
+        }
+    }
+    state
+}
+
+/// The buttons to press, as indices into the machine's buttons, to turn on exactly the desired
+/// lights with as few presses as possible. Pressing a button twice undoes it, so each button is
+/// pressed at most once and this is a linear system over GF(2), with one bitmask of buttons per
+/// light. Returns `None` if no combination of buttons gives the desired lights.
+pub fn buttons_for_lights(machine: &Machine) -> Option<Vec<usize>> {
+    let buttons = machine.buttons.len();
+    let mut equations: Vec<(u64, bool)> = machine
+        .desired_lights
+        .iter()
+        .enumerate()
+        .map(|(light, desired)| {
+            let mask = machine
+                .buttons
+                .iter()
+                .enumerate()
+                .filter(|(_, b)| b.toggles_lights.contains(&light))
+                .fold(0, |mask, (i, _)| mask | 1 << i);
+            (mask, *desired)
+        })
+        .collect();
+    let mut pivots: Vec<(usize, u64, bool)> = vec![];
+    for button in 0..buttons {
+        let bit = 1 << button;
+        let Some(row) = equations.iter().position(|(mask, _)| mask & bit != 0) else {
+            continue;
+        };
+        let (mask, desired) = equations.swap_remove(row);
+        for (other, other_desired) in equations
+            .iter_mut()
+            .map(|(m, d)| (m, d))
+            .chain(pivots.iter_mut().map(|(_, m, d)| (m, d)))
+        {
+            if *other & bit != 0 {
+                *other ^= mask;
+                *other_desired ^= desired;
+            }
+        }
+        pivots.push((button, mask, desired));
+    }
+    if equations.iter().any(|(_, desired)| *desired) {
+        return None;
+    }
+    let free: Vec<usize> = (0..buttons)
+        .filter(|b| pivots.iter().all(|(pivot, _, _)| pivot != b))
+        .collect();
+    // Every solution is the one with no free buttons pressed plus some combination of the
+    // null space vectors, one per free button
+    let particular = pivots
+        .iter()
+        .filter(|(_, _, desired)| *desired)
+        .fold(0u64, |presses, (pivot, _, _)| presses | 1 << pivot);
+    let null_space: Vec<u64> = free
+        .iter()
+        .map(|f| {
+            pivots
+                .iter()
+                .filter(|(_, mask, _)| mask & 1 << f != 0)
+                .fold(1 << f, |presses, (pivot, _, _)| presses | 1 << pivot)
+        })
+        .collect();
+    let best = (0..1u64 << free.len())
+        .map(|combination| {
+            null_space
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| combination & 1 << i != 0)
+                .fold(particular, |presses, (_, v)| presses ^ v)
+        })
+        .min_by_key(|presses| presses.count_ones())
+        .unwrap();
+    Some((0..buttons).filter(|b| best & 1 << b != 0).collect())
+}
+
+fn button_presses_for_lights(machine: &Machine) -> usize {
+    let pressed =
+        buttons_for_lights(machine).expect("no combination of buttons gives the desired lights");
+    debug_assert_eq!(
+        compute_state(
+            machine,
+            &(0..machine.buttons.len())
+                .map(|b| pressed.contains(&b) as usize)
+                .collect::<Vec<usize>>()
+        ),
+        machine.desired_lights
+    );
+    pressed.len()
+}
+
+fn parse_input(input: &str) -> Result<Vec<Machine>, ParseError> {
+    lines(input)
+        .map(|line| {
+            line.text
+                .parse::<Machine>()
+                .map_err(|e| e.below(line.number - 1))
+        })
+        .collect()
+}
+
+fn compute_joltages(machine: &Machine, button_presses: &[u16]) -> Vec<u16> {
+    let mut joltages: Vec<u16> = vec![0; machine.desired_joltages.len()];
+    for (button_idx, presses) in button_presses.iter().enumerate() {
+        for light in machine.buttons[button_idx].toggles_lights.iter() {
+            joltages[*light] += presses;
+        }
+    }
+    joltages
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+// One row of the joltage equations, `coefficients · presses = total`
+#[derive(Clone, Debug)]
+struct Equation {
+    coefficients: Vec<i64>,
+    total: i64,
+}
+
+impl Equation {
+    fn normalize(&mut self) {
+        let divisor = self
+            .coefficients
+            .iter()
+            .fold(self.total, |divisor, c| gcd(divisor, *c));
+        if divisor > 1 {
+            self.coefficients.iter_mut().for_each(|c| *c /= divisor);
+            self.total /= divisor;
+        }
+    }
+}
+
+// Reduces the equations to reduced row echelon form, keeping everything in integers, and
+// returns the pivot rows paired with the button each one solves for
+fn eliminate(mut equations: Vec<Equation>, buttons: usize) -> Option<Vec<(usize, Equation)>> {
+    let mut pivots: Vec<(usize, Equation)> = vec![];
+    for button in 0..buttons {
+        let Some(row) = equations.iter().position(|e| e.coefficients[button] != 0) else {
+            continue;
+        };
+        let mut pivot = equations.swap_remove(row);
+        if pivot.coefficients[button] < 0 {
+            pivot.coefficients.iter_mut().for_each(|c| *c = -*c);
+            pivot.total = -pivot.total;
+        }
+        let scale = pivot.coefficients[button];
+        for other in equations
+            .iter_mut()
+            .chain(pivots.iter_mut().map(|(_, e)| e))
+        {
+            let factor = other.coefficients[button];
+            if factor == 0 {
+                continue;
+            }
+            for (c, p) in other.coefficients.iter_mut().zip(pivot.coefficients.iter()) {
+                *c = *c * scale - p * factor;
+            }
+            other.total = other.total * scale - pivot.total * factor;
+            other.normalize();
+        }
+        pivots.push((button, pivot));
+    }
+    // Whatever's left has no buttons in it, so it had better want nothing
+    if equations.iter().any(|e| e.total != 0) {
+        return None;
+    }
+    Some(pivots)
+}
+
+struct JoltageSearch<'a> {
+    pivots: &'a [(usize, Equation)],
+    free: &'a [usize],
+    max_presses: &'a [i64],
+    presses: Vec<i64>,
+    best: Option<(i64, Vec<i64>)>,
+}
+
+impl JoltageSearch<'_> {
+    fn search(&mut self, free_idx: usize, pressed: i64) {
+        if self.best.as_ref().is_some_and(|(best, _)| pressed >= *best) {
+            return;
+        }
+        if let Some(&button) = self.free.get(free_idx) {
+            for count in 0..=self.max_presses[button] {
+                self.presses[button] = count;
+                self.search(free_idx + 1, pressed + count);
+            }
+            self.presses[button] = 0;
+            return;
+        }
+        let mut total = pressed;
+        for (button, equation) in self.pivots {
+            let rest: i64 = self
+                .free
+                .iter()
+                .map(|free| equation.coefficients[*free] * self.presses[*free])
+                .sum();
+            let remaining = equation.total - rest;
+            let scale = equation.coefficients[*button];
+            if remaining < 0 || remaining % scale != 0 {
+                return;
+            }
+            self.presses[*button] = remaining / scale;
+            total += remaining / scale;
+        }
+        if self.best.as_ref().is_none_or(|(best, _)| total < *best) {
+            self.best = Some((total, self.presses.clone()));
+        }
+    }
+}
+
+// Solves `buttons × presses = joltages` for the fewest total presses. Elimination leaves each
+// pivot button's presses determined by the free buttons, and no button can be pressed more
+// times than the smallest joltage it adds to, so searching the free buttons' presses is small.
+// `None` if no presses give the desired joltages.
+fn joltage_presses(machine: &Machine) -> Option<Vec<u16>> {
+    let buttons = machine.buttons.len();
+    let equations = machine
+        .desired_joltages
+        .iter()
+        .enumerate()
+        .map(|(counter, desired)| Equation {
+            coefficients: machine
+                .buttons
+                .iter()
+                .map(|b| b.toggles_lights.contains(&counter) as i64)
+                .collect(),
+            total: *desired as i64,
+        })
+        .collect();
+    let pivots = eliminate(equations, buttons)?;
+    let free: Vec<usize> = (0..buttons)
+        .filter(|b| pivots.iter().all(|(pivot, _)| pivot != b))
+        .collect();
+    let max_presses: Vec<i64> = machine
+        .buttons
+        .iter()
+        .map(|b| {
+            b.toggles_lights
+                .iter()
+                .map(|counter| machine.desired_joltages[*counter] as i64)
+                .min()
+                .unwrap_or(0)
+        })
+        .collect();
+    let mut search = JoltageSearch {
+        pivots: &pivots,
+        free: &free,
+        max_presses: &max_presses,
+        presses: vec![0; buttons],
+        best: None,
+    };
+    search.search(0, 0);
+    let (_, presses) = search.best?;
+    Some(presses.into_iter().map(|p| p as u16).collect())
+}
+
+fn button_presses_for_joltages(machine: &Machine) -> usize {
+    let presses =
+        joltage_presses(machine).expect("no presses of the buttons give the desired joltages");
+    debug_assert_eq!(
+        compute_joltages(machine, &presses),
+        machine.desired_joltages
+    );
+    presses.into_iter().map(|n| n as usize).sum()
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Config = ();
+    type Input = Vec<Machine>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str, _config: &()) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(machines: &Self::Input, _config: &()) -> usize {
+        machines.iter().map(button_presses_for_lights).sum()
+    }
+
+    fn part2(machines: &Self::Input, _config: &()) -> usize {
+        machines.iter().map(button_presses_for_joltages).sum()
+    }
+
+    fn examples() -> Vec<Example<Self::Config>> {
+        vec![Example {
+            name: "basic",
+            input: BASIC_INPUT,
+            config: (),
+            parts: &[1, 2],
+        }]
+    }
+}
+
+pub fn part1(input: &str) -> usize {
+    Day10::solve_part1(input, &()).unwrap()
+}
+
+pub fn part2(input: &str) -> usize {
+    Day10::solve_part2(input, &()).unwrap()
+}
+
+pub const BASIC_INPUT: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BASIC_INPUT, Button, Machine, buttons_for_lights, compute_joltages, compute_state,
+        joltage_presses, parse_input, part1, part2,
+    };
+
+    fn make_machine() -> Machine {
+        Machine {
+            desired_lights: vec![false, true, true, false],
+            buttons: vec![
+                Button {
+                    toggles_lights: vec![3],
+                },
+                Button {
+                    toggles_lights: vec![1, 3],
+                },
+                Button {
+                    toggles_lights: vec![2],
+                },
+                Button {
+                    toggles_lights: vec![2, 3],
+                },
+                Button {
+                    toggles_lights: vec![0, 2],
+                },
+                Button {
+                    toggles_lights: vec![0, 1],
+                },
+            ],
+            desired_joltages: vec![3, 5, 4, 7],
+        }
+    }
+
+    #[test]
+    fn compute_state_works() {
+        let machine = make_machine();
+        let button_presses = vec![0, 0, 0, 0, 1, 1];
+        assert_eq!(
+            compute_state(&machine, &button_presses),
+            machine.desired_lights
+        );
+    }
+
+    #[test]
+    fn parse_errors_point_at_bad_token() {
+        let input = "[.##.] (3) (1,3) {3,5,4,7}\n[.##.] (3) (1,4) {3,5,4,7}";
+        let err = parse_input(input).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 15));
+        assert_eq!(err.expected, "a light index below 4");
+        let err = parse_input("[.##.] (3) {3,5,4}").unwrap_err();
+        assert_eq!(err.expected, "4 joltages");
+    }
+
+    #[test]
+    fn lights_report_which_buttons() {
+        let machine = make_machine();
+        let pressed = buttons_for_lights(&machine).unwrap();
+        assert_eq!(pressed.len(), 2);
+        let mut presses = vec![0; machine.buttons.len()];
+        pressed.iter().for_each(|b| presses[*b] = 1);
+        assert_eq!(compute_state(&machine, &presses), machine.desired_lights);
+    }
+
+    #[test]
+    fn unreachable_lights_have_no_buttons() {
+        let machine = parse_input("[#.] (0,1) {1,0}").unwrap().remove(0);
+        assert_eq!(buttons_for_lights(&machine), None);
+        assert_eq!(joltage_presses(&machine), None);
+    }
+
+    #[test]
+    fn single_machine() {
+        assert_eq!(part1(BASIC_INPUT.lines().next().unwrap()), 2);
+    }
+
+    #[test]
+    fn basic_test_part1() {
+        assert_eq!(part1(BASIC_INPUT), 7);
+    }
+
+    #[test]
+    fn compute_joltages_works() {
+        let machine = make_machine();
+        let button_presses = vec![1, 3, 0, 3, 1, 2];
+        assert_eq!(
+            compute_joltages(&machine, &button_presses),
+            machine.desired_joltages
+        );
+    }
+
+    #[test]
+    fn single_machine_joltage() {
+        assert_eq!(part2(BASIC_INPUT.lines().next().unwrap()), 10);
+    }
+
+    #[test]
+    fn basic_test_part2() {
+        assert_eq!(part2(BASIC_INPUT), 33);
+    }
+}
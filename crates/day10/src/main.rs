@@ -1,231 +1,12 @@
-use std::{
-    collections::{HashSet, VecDeque},
-    fs::read_to_string,
-    str::FromStr,
-};
-
-#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone)]
-struct Button {
-    toggles_lights: Vec<usize>,
-}
-
-#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone)]
-struct Machine {
-    desired_lights: Vec<bool>,
-    buttons: Vec<Button>,
-    desired_joltages: Vec<u16>,
-}
-
-impl FromStr for Machine {
-    type Err = ();
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (desired_lights, buttons) = s.split_once(']').unwrap();
-        let desired_lights = &desired_lights[1..];
-        let desired_lights = desired_lights.chars().map(|c| c == '#').collect();
-        let (buttons, desired_joltages) = buttons.split_once('{').unwrap();
-        let buttons = buttons
-            .trim()
-            .split_whitespace()
-            .map(|tup| &tup[1..tup.len() - 1])
-            .map(|lst| Button {
-                toggles_lights: lst
-                    .split(',')
-                    .map(|num| num.parse::<usize>().unwrap())
-                    .collect(),
-            })
-            .collect();
-        let desired_joltages = &desired_joltages[..desired_joltages.len() - 1];
-        let desired_joltages = desired_joltages
-            .split(',')
-            .map(|num| num.parse::<u16>().unwrap())
-            .collect();
-        Ok(Machine {
-            desired_lights,
-            buttons,
-            desired_joltages,
-        })
-    }
-}
-
-fn compute_state(machine: &Machine, button_presses: &[usize]) -> Vec<bool> {
-    let mut state: Vec<bool> = vec![false; machine.desired_lights.len()];
-    for press in 0..button_presses.len() {
-        if button_presses[press] % 2 == 1 {
-            for light in machine.buttons[press].toggles_lights.iter() {
-                state[*light] = !state[*light];
-            }
-        }
-    }
-    state
-}
-
-fn button_presses_for_lights(machine: &Machine) -> usize {
-    let mut queue: VecDeque<Vec<usize>> = VecDeque::new();
-    let mut queued_to_visit: HashSet<Vec<usize>> = HashSet::new();
-    queue.push_back(vec![0; machine.buttons.len()]);
-    while let Some(presses) = queue.pop_front() {
-        if compute_state(machine, &presses) == machine.desired_lights {
-            return presses.into_iter().sum();
-        }
-        for button in 0..machine.buttons.len() {
-            let mut next_presses = presses.clone();
-            next_presses[button] += 1;
-            if !queued_to_visit.contains(&next_presses) {
-                queue.push_back(next_presses.clone());
-                queued_to_visit.insert(next_presses);
-            }
-        }
-    }
-    panic!("No solution found");
-}
-
-fn parse_input(input: &str) -> Vec<Machine> {
-    input
-        .lines()
-        .map(|line| line.parse::<Machine>().unwrap())
-        .collect()
-}
-
-fn part1(input: &str) -> usize {
-    parse_input(input)
-        .iter()
-        .map(button_presses_for_lights)
-        .sum()
-}
-
-fn compute_joltages(machine: &Machine, button_presses: &[u16]) -> Vec<u16> {
-    let mut joltages: Vec<u16> = vec![0; machine.desired_joltages.len()];
-    for (button_idx, presses) in button_presses.iter().enumerate() {
-        for light in machine.buttons[button_idx].toggles_lights.iter() {
-            joltages[*light] += presses;
-        }
-    }
-    joltages
-}
-
-fn button_presses_for_joltages(machine: &Machine) -> usize {
-    let mut queue: VecDeque<Vec<u16>> = VecDeque::new();
-    let mut queued_to_visit: HashSet<Vec<u16>> = HashSet::new();
-    let mut iterations: usize = 0;
-    let start_state = vec![0; machine.buttons.len()];
-    queue.push_back(start_state.clone());
-    queued_to_visit.insert(start_state);
-    while let Some(presses) = queue.pop_front() {
-        iterations += 1;
-        if iterations % 1_000_000 == 0 {
-            println!("iteration {}M", iterations / 1_000_000);
-        }
-        let joltages = compute_joltages(machine, &presses);
-        if joltages == machine.desired_joltages {
-            println!("{} iterations", iterations);
-            return presses.into_iter().map(|n| n as usize).sum();
-        }
-        if joltages
-            .iter()
-            .zip(machine.desired_joltages.iter())
-            .any(|(c, desired)| c > desired)
-        {
-            continue;
-        }
-
-        for button in 0..machine.buttons.len() {
-            let mut next_presses = presses.clone();
-            next_presses[button] += 1;
-            if !queued_to_visit.contains(&next_presses) {
-                queue.push_back(next_presses.clone());
-                queued_to_visit.insert(next_presses);
-            }
-        }
-    }
-    panic!("No solution found");
-}
-
-fn part2(input: &str) -> usize {
-    parse_input(input)
-        .iter()
-        .map(button_presses_for_joltages)
-        .sum()
-}
+use common::{Solution, parse_or_exit};
+use day10::Day10;
+use std::fs::read_to_string;
 
 fn main() {
     let input = read_to_string("input.txt").unwrap();
-    let p1_answer = part1(&input);
-    println!("part 1: {}", p1_answer);
-    let p2_answer = part2(&input);
-    println!("part 2: {}", p2_answer);
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::{Button, Machine, compute_joltages, compute_state, part1, part2};
-    const BASIC_INPUT: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
-[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
-[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
-
-    fn make_machine() -> Machine {
-        Machine {
-            desired_lights: vec![false, true, true, false],
-            buttons: vec![
-                Button {
-                    toggles_lights: vec![3],
-                },
-                Button {
-                    toggles_lights: vec![1, 3],
-                },
-                Button {
-                    toggles_lights: vec![2],
-                },
-                Button {
-                    toggles_lights: vec![2, 3],
-                },
-                Button {
-                    toggles_lights: vec![0, 2],
-                },
-                Button {
-                    toggles_lights: vec![0, 1],
-                },
-            ],
-            desired_joltages: vec![3, 5, 4, 7],
-        }
-    }
-
-    #[test]
-    fn compute_state_works() {
-        let machine = make_machine();
-        let button_presses = vec![0, 0, 0, 0, 1, 1];
-        assert_eq!(
-            compute_state(&machine, &button_presses),
-            machine.desired_lights
-        );
-    }
-
-    #[test]
-    fn single_machine() {
-        assert_eq!(part1(BASIC_INPUT.lines().next().unwrap()), 2);
-    }
-
-    #[test]
-    fn basic_test_part1() {
-        assert_eq!(part1(BASIC_INPUT), 7);
-    }
-
-    #[test]
-    fn compute_joltages_works() {
-        let machine = make_machine();
-        let button_presses = vec![1, 3, 0, 3, 1, 2];
-        assert_eq!(
-            compute_joltages(&machine, &button_presses),
-            machine.desired_joltages
-        );
-    }
-
-    #[test]
-    fn single_machine_joltage() {
-        assert_eq!(part2(BASIC_INPUT.lines().next().unwrap()), 10);
-    }
-
-    #[test]
-    fn basic_test_part2() {
-        assert_eq!(part2(BASIC_INPUT), 33);
-    }
+    let machines = parse_or_exit::<Day10>("input.txt", &input, &());
+    let p1_answer = Day10::part1(&machines, &());
+    println!("day 10 part 1: {}", p1_answer);
+    let p2_answer = Day10::part2(&machines, &());
+    println!("day 10 part 2: {}", p2_answer);
 }
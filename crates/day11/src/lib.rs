@@ -0,0 +1,186 @@
+use common::{Example, ParseError, Solution, lines};
+use petgraph::{
+    algo::{all_simple_paths, is_cyclic_directed},
+    graph::{DefaultIx, DiGraph, NodeIndex},
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::RandomState,
+};
+
+type Idx = NodeIndex<DefaultIx>;
+
+#[derive(Clone, Debug)]
+pub struct Input {
+    graph: DiGraph<(), ()>,
+    lookup: HashMap<String, Idx>,
+}
+
+impl Input {
+    // Panics naming the device if the input never mentions it
+    fn node(&self, name: &str) -> Idx {
+        *self
+            .lookup
+            .get(name)
+            .unwrap_or_else(|| panic!("no device named `{}` in the input", name))
+    }
+}
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let mut graph = DiGraph::new();
+    let mut lookup: HashMap<String, Idx> = HashMap::new();
+    for line in lines(input) {
+        let (from_node, to_nodes) = line.split_once(line.text, ": ")?;
+        if from_node.is_empty() {
+            return Err(line.error(from_node, "a device name"));
+        }
+        let to_nodes: Vec<&str> = to_nodes.split_whitespace().collect();
+        if !lookup.contains_key(from_node) {
+            lookup.insert(from_node.to_string(), graph.add_node(()));
+        }
+        let from_idx = *lookup.get(from_node).unwrap();
+        for to_node in to_nodes {
+            if !lookup.contains_key(to_node) {
+                lookup.insert(to_node.to_string(), graph.add_node(()));
+            }
+            let to_idx = *lookup.get(to_node).unwrap();
+            graph.add_edge(from_idx, to_idx, ());
+        }
+    }
+    Ok(Input { graph, lookup })
+}
+
+fn get_path_count(input: &Input) -> usize {
+    let start = input.node("you");
+    let end = input.node("out");
+    all_simple_paths::<Vec<_>, _, RandomState>(&input.graph, start, end, 0, None).count()
+}
+
+fn count_paths(graph: &DiGraph<(), ()>, from: Idx, to: Idx) -> usize {
+    let mut queue: VecDeque<Idx> = VecDeque::new();
+    let mut paths: HashMap<Idx, usize> = HashMap::new();
+    queue.push_back(from);
+    paths.insert(from, 1);
+    while let Some(node) = queue.pop_front() {
+        if node == to {
+            continue;
+        }
+        let cur_count = *paths.get(&node).unwrap();
+        for neighbor in graph.neighbors(node) {
+            if let Some(count) = paths.get_mut(&neighbor) {
+                *count += cur_count;
+            } else {
+                paths.insert(neighbor, cur_count);
+            }
+            queue.push_back(neighbor);
+        }
+    }
+    paths.get(&to).copied().unwrap_or(0)
+}
+
+fn get_path_count_p2(input: &Input) -> usize {
+    let svr = input.node("svr");
+    let dac = input.node("dac");
+    let fft = input.node("fft");
+    let out = input.node("out");
+    let svr_to_dac = count_paths(&input.graph, svr, dac);
+    let svr_to_fft = count_paths(&input.graph, svr, fft);
+    let dac_to_fft = count_paths(&input.graph, dac, fft);
+    let fft_to_dac = count_paths(&input.graph, fft, dac);
+    let fft_to_out = count_paths(&input.graph, fft, out);
+    let dac_to_out = count_paths(&input.graph, dac, out);
+    svr_to_dac * dac_to_fft * fft_to_out + svr_to_fft * fft_to_dac * dac_to_out
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Config = ();
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str, _config: &()) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input, _config: &()) -> usize {
+        get_path_count(input)
+    }
+
+    fn part2(input: &Self::Input, _config: &()) -> usize {
+        assert!(!is_cyclic_directed(&input.graph));
+        get_path_count_p2(input)
+    }
+
+    fn examples() -> Vec<Example<Self::Config>> {
+        vec![
+            Example {
+                name: "basic",
+                input: BASIC_INPUT,
+                config: (),
+                parts: &[1],
+            },
+            Example {
+                name: "basic p2",
+                input: BASIC_INPUT_P2,
+                config: (),
+                parts: &[2],
+            },
+        ]
+    }
+}
+
+pub fn part1(input: &str) -> usize {
+    Day11::solve_part1(input, &()).unwrap()
+}
+
+pub fn part2(input: &str) -> usize {
+    Day11::solve_part2(input, &()).unwrap()
+}
+
+pub const BASIC_INPUT: &str = "aaa: you hhh
+you: bbb ccc
+bbb: ddd eee
+ccc: ddd eee fff
+ddd: ggg
+eee: out
+fff: out
+ggg: out
+hhh: ccc fff iii
+iii: out";
+
+pub const BASIC_INPUT_P2: &str = "svr: aaa bbb
+aaa: fft
+fft: ccc
+bbb: tty
+tty: ccc
+ccc: ddd eee
+ddd: hub
+hub: fff
+eee: dac
+dac: fff
+fff: ggg hhh
+ggg: out
+hhh: out";
+
+#[cfg(test)]
+mod tests {
+    use crate::{BASIC_INPUT, BASIC_INPUT_P2, part1, part2};
+
+    #[test]
+    fn basic_test_part1() {
+        assert_eq!(part1(BASIC_INPUT), 5);
+    }
+
+    #[test]
+    fn basic_test_part2() {
+        assert_eq!(part2(BASIC_INPUT_P2), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "no device named `svr` in the input")]
+    fn missing_device_is_named() {
+        part2(BASIC_INPUT);
+    }
+}
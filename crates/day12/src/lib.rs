@@ -0,0 +1,492 @@
+use common::{Example, Line, ParseError, Solution, Unsolved, lines};
+use std::str::FromStr;
+
+const SHAPE_SIZE: usize = 3;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+struct Shape {
+    grid: [[bool; SHAPE_SIZE]; SHAPE_SIZE],
+}
+
+impl FromStr for Shape {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut shape = Shape::default();
+        let mut rows = 0;
+        for (y, line) in lines(s).enumerate() {
+            if y >= SHAPE_SIZE {
+                return Err(line.error(line.text, "the end of the shape"));
+            }
+            let mut columns = 0;
+            for (x, c) in line.text.char_indices() {
+                if x >= SHAPE_SIZE {
+                    return Err(line.error(&line.text[x..], "the end of the row"));
+                }
+                shape.grid[y][x] = match c {
+                    '#' => true,
+                    '.' => false,
+                    _ => return Err(line.error(&line.text[x..x + c.len_utf8()], "`#` or `.`")),
+                };
+                columns += 1;
+            }
+            if columns < SHAPE_SIZE {
+                return Err(line.end_error("`#` or `.`"));
+            }
+            rows += 1;
+        }
+        if rows < SHAPE_SIZE {
+            return Err(ParseError::end_of_input(s, "another row of the shape"));
+        }
+        Ok(shape)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Region {
+    x: u8,
+    y: u8,
+    present_counts: Vec<u8>,
+}
+
+impl FromStr for Region {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let line = Line { number: 1, text: s };
+        let (coords, present_counts) = line.split_once(s, ": ")?;
+        let (x, y) = line.split_once(coords, "x")?;
+        let x = line.parse::<u8>(x, "a region width")?;
+        let y = line.parse::<u8>(y, "a region height")?;
+        let present_counts = present_counts
+            .split(' ')
+            .map(|count| line.parse::<u8>(count, "a present count"))
+            .collect::<Result<Vec<u8>, ParseError>>()?;
+        Ok(Region {
+            x,
+            y,
+            present_counts,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Input {
+    shapes: Vec<Shape>,
+    regions: Vec<Region>,
+}
+
+fn parse_input(input: &str, shape_count: usize) -> Result<Input, ParseError> {
+    let mut shapes = vec![];
+    let mut iter = lines(input);
+    let mut next_line = |expected: &str| {
+        iter.next()
+            .ok_or_else(|| ParseError::end_of_input(input, expected))
+    };
+    for i in 0..shape_count {
+        let label = next_line("a shape label")?;
+        if label.text != format!("{}:", i) {
+            return Err(label.error(label.text, format!("`{}:`", i)));
+        }
+        // get shape string
+        let mut shape = String::default();
+        for _ in 0..SHAPE_SIZE {
+            shape.push_str(next_line("another row of the shape")?.text);
+            shape.push('\n');
+        }
+        shapes.push(shape.parse::<Shape>().map_err(|e| e.below(label.number))?);
+        // skip newline
+        let blank = next_line("a blank line")?;
+        if !blank.text.trim().is_empty() {
+            return Err(blank.error(blank.text, "a blank line"));
+        }
+    }
+    let regions = iter
+        .map(|line| {
+            let mut region = line
+                .text
+                .parse::<Region>()
+                .map_err(|e| e.below(line.number - 1))?;
+            if region.present_counts.len() > shape_count {
+                return Err(line.end_error(format!("at most {} present counts", shape_count)));
+            }
+            region.present_counts.resize(shape_count, 0);
+            Ok(region)
+        })
+        .collect::<Result<Vec<Region>, ParseError>>()?;
+    Ok(Input { shapes, regions })
+}
+
+fn free_area(region: &[Vec<bool>]) -> usize {
+    region
+        .iter()
+        .map(|l| l.iter().filter(|b| !**b).count())
+        .sum()
+}
+
+fn rotate(shape: &Shape, rotation: Rotation) -> Shape {
+    let grid = match rotation {
+        Rotation::Zero => shape.grid,
+        Rotation::One => [
+            [shape.grid[2][0], shape.grid[1][0], shape.grid[0][0]],
+            [shape.grid[2][1], shape.grid[1][1], shape.grid[0][1]],
+            [shape.grid[2][2], shape.grid[1][2], shape.grid[0][2]],
+        ],
+        Rotation::Two => [
+            [shape.grid[2][2], shape.grid[2][1], shape.grid[2][0]],
+            [shape.grid[1][2], shape.grid[1][1], shape.grid[1][0]],
+            [shape.grid[0][2], shape.grid[0][1], shape.grid[0][0]],
+        ],
+        Rotation::Three => [
+            [shape.grid[0][2], shape.grid[1][2], shape.grid[2][2]],
+            [shape.grid[0][1], shape.grid[1][1], shape.grid[2][1]],
+            [shape.grid[0][0], shape.grid[1][0], shape.grid[2][0]],
+        ],
+    };
+    Shape { grid }
+}
+
+fn flip(shape: &Shape) -> Shape {
+    let mut grid = shape.grid;
+    for row in grid.iter_mut() {
+        row.reverse();
+    }
+    Shape { grid }
+}
+
+fn orient(shape: &Shape, rotation: Rotation, flipped: bool) -> Shape {
+    let rotated = rotate(shape, rotation);
+    if flipped { flip(&rotated) } else { rotated }
+}
+
+fn area(shape: &Shape) -> usize {
+    shape
+        .grid
+        .iter()
+        .map(|l| l.iter().filter(|b| **b).count())
+        .sum()
+}
+
+fn simple_check(region: &[Vec<bool>], shape: &Shape) -> bool {
+    free_area(region) >= area(shape)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Rotation {
+    Zero,
+    One,
+    Two,
+    Three,
+}
+
+const ROTATIONS: [Rotation; 4] = [
+    Rotation::Zero,
+    Rotation::One,
+    Rotation::Two,
+    Rotation::Three,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Placement {
+    x: u8,
+    y: u8,
+    rotation: Rotation,
+    flipped: bool,
+}
+
+// Gets the distinct rotations and reflections of a shape
+fn orientations(shape: &Shape) -> Vec<(Rotation, bool)> {
+    let mut seen: Vec<Shape> = vec![];
+    let mut result = vec![];
+    for flipped in [false, true] {
+        for rotation in ROTATIONS {
+            let oriented = orient(shape, rotation, flipped);
+            if !seen.contains(&oriented) {
+                seen.push(oriented);
+                result.push((rotation, flipped));
+            }
+        }
+    }
+    result
+}
+
+fn first_free(region: &[Vec<bool>]) -> Option<(usize, usize)> {
+    region
+        .iter()
+        .enumerate()
+        .find_map(|(y, row)| row.iter().position(|filled| !*filled).map(|x| (x, y)))
+}
+
+// Gets a list of spots a shape can be placed. Every cell before the first free cell is already
+// decided, so only placements whose first filled cell lands on the first free cell are returned.
+fn valid_placements(region: &[Vec<bool>], shape: &Shape) -> Vec<Placement> {
+    let Some((free_x, free_y)) = first_free(region) else {
+        return vec![];
+    };
+    let mut placements = vec![];
+    for (rotation, flipped) in orientations(shape) {
+        let oriented = orient(shape, rotation, flipped);
+        let Some((anchor_x, anchor_y)) = (0..SHAPE_SIZE)
+            .flat_map(|y| (0..SHAPE_SIZE).map(move |x| (x, y)))
+            .find(|(x, y)| oriented.grid[*y][*x])
+        else {
+            continue;
+        };
+        if free_x < anchor_x || free_y < anchor_y {
+            continue;
+        }
+        let x = free_x - anchor_x;
+        let y = free_y - anchor_y;
+        let fits = (0..SHAPE_SIZE).all(|dy| {
+            (0..SHAPE_SIZE).all(|dx| {
+                !oriented.grid[dy][dx]
+                    || region
+                        .get(y + dy)
+                        .and_then(|row| row.get(x + dx))
+                        .is_some_and(|filled| !*filled)
+            })
+        });
+        if fits {
+            placements.push(Placement {
+                x: x as u8,
+                y: y as u8,
+                rotation,
+                flipped,
+            });
+        }
+    }
+    placements
+}
+
+fn place(region: &[Vec<bool>], shape: &Shape, placement: &Placement) -> Vec<Vec<bool>> {
+    let oriented = orient(shape, placement.rotation, placement.flipped);
+    let mut region = region.to_vec();
+    for dy in 0..SHAPE_SIZE {
+        for dx in 0..SHAPE_SIZE {
+            if oriented.grid[dy][dx] {
+                region[placement.y as usize + dy][placement.x as usize + dx] = true;
+            }
+        }
+    }
+    region
+}
+
+// Counts free cells in connected pockets too small to hold any of the remaining presents
+fn wasted_area(region: &[Vec<bool>], smallest: usize) -> usize {
+    let mut seen: Vec<Vec<bool>> = region.to_vec();
+    let mut wasted = 0;
+    for y in 0..region.len() {
+        for x in 0..region[y].len() {
+            if seen[y][x] {
+                continue;
+            }
+            seen[y][x] = true;
+            let mut stack = vec![(x, y)];
+            let mut size = 0;
+            while let Some((x, y)) = stack.pop() {
+                size += 1;
+                let neighbors = [
+                    (x.wrapping_sub(1), y),
+                    (x + 1, y),
+                    (x, y.wrapping_sub(1)),
+                    (x, y + 1),
+                ];
+                for (nx, ny) in neighbors {
+                    if seen.get(ny).and_then(|row| row.get(nx)) == Some(&false) {
+                        seen[ny][nx] = true;
+                        stack.push((nx, ny));
+                    }
+                }
+            }
+            if size < smallest {
+                wasted += size;
+            }
+        }
+    }
+    wasted
+}
+
+// Exact cover search over the cells of the region. The first free cell is either covered by a
+// present placed there, or left empty, which is only allowed while there is spare area left over.
+fn fit_presents(region: &[Vec<bool>], shapes: &[Shape], counts: &mut [u8], slack: usize) -> bool {
+    if counts.iter().all(|c| *c == 0) {
+        return true;
+    }
+    let smallest = shapes
+        .iter()
+        .zip(counts.iter())
+        .filter(|(_, count)| **count > 0)
+        .map(|(shape, _)| area(shape))
+        .min()
+        .unwrap();
+    if wasted_area(region, smallest) > slack {
+        return false;
+    }
+    let Some((free_x, free_y)) = first_free(region) else {
+        return false;
+    };
+    for (shape_idx, shape) in shapes.iter().enumerate() {
+        if counts[shape_idx] == 0 || !simple_check(region, shape) {
+            continue;
+        }
+        counts[shape_idx] -= 1;
+        for placement in valid_placements(region, shape) {
+            if fit_presents(&place(region, shape, &placement), shapes, counts, slack) {
+                counts[shape_idx] += 1;
+                return true;
+            }
+        }
+        counts[shape_idx] += 1;
+    }
+    if slack == 0 {
+        return false;
+    }
+    let mut region = region.to_vec();
+    region[free_y][free_x] = true;
+    fit_presents(&region, shapes, counts, slack - 1)
+}
+
+fn region_fits(region: &Region, shapes: &[Shape]) -> bool {
+    let region_area = region.x as usize * region.y as usize;
+    let needed_area: usize = region
+        .present_counts
+        .iter()
+        .zip(shapes.iter())
+        .map(|(count, shape)| *count as usize * area(shape))
+        .sum();
+    if needed_area > region_area {
+        return false;
+    }
+    // Every present fits in its own 3x3 block, so there's no need to search if there are enough
+    // of those.
+    let total_presents: usize = region.present_counts.iter().map(|c| *c as usize).sum();
+    let blocks = (region.x as usize / SHAPE_SIZE) * (region.y as usize / SHAPE_SIZE);
+    if total_presents <= blocks {
+        return true;
+    }
+    // Presents can be rotated, so lay the region out with its short side as the row length. That
+    // keeps the frontier of the search small.
+    let width = region.x.min(region.y) as usize;
+    let height = region.x.max(region.y) as usize;
+    let grid = vec![vec![false; width]; height];
+    let mut counts = region.present_counts.clone();
+    fit_presents(&grid, shapes, &mut counts, region_area - needed_area)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Config {
+    pub shape_count: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { shape_count: 6 }
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Config = Config;
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = Unsolved;
+
+    fn parse(input: &str, config: &Config) -> Result<Self::Input, ParseError> {
+        parse_input(input, config.shape_count)
+    }
+
+    fn part1(input: &Self::Input, _config: &Config) -> usize {
+        input
+            .regions
+            .iter()
+            .filter(|region| region_fits(region, &input.shapes))
+            .count()
+    }
+
+    fn part2(_input: &Self::Input, _config: &Config) -> Unsolved {
+        Unsolved
+    }
+
+    fn examples() -> Vec<Example<Config>> {
+        vec![Example {
+            name: "basic",
+            input: BASIC_INPUT,
+            config: Config::default(),
+            parts: &[1],
+        }]
+    }
+}
+
+pub fn part1(input: &str, shape_count: usize) -> usize {
+    Day12::solve_part1(input, &Config { shape_count }).unwrap()
+}
+
+/*
+pub fn part2(input: &str) -> usize {
+    todo!()
+}
+*/
+
+pub const BASIC_INPUT: &str = "0:
+###
+##.
+##.
+
+1:
+###
+##.
+.##
+
+2:
+.##
+###
+##.
+
+3:
+##.
+###
+##.
+
+4:
+###
+#..
+###
+
+5:
+###
+.#.
+###
+
+4x4: 0 0 0 0 2 0
+12x5: 1 0 1 0 2 2
+12x5: 1 0 1 0 3 2";
+
+#[cfg(test)]
+mod tests {
+    use crate::{BASIC_INPUT, Rotation, Shape, orientations, part1, rotate};
+    // use crate::part2;
+
+    #[test]
+    fn rotate_works() {
+        let shape = "###\n#..\n#..".parse::<Shape>().unwrap();
+        let rotated = "###\n..#\n..#".parse::<Shape>().unwrap();
+        assert_eq!(rotate(&shape, Rotation::One), rotated);
+        let mut full_turn = shape;
+        for _ in 0..4 {
+            full_turn = rotate(&full_turn, Rotation::Three);
+        }
+        assert_eq!(full_turn, shape);
+        assert_eq!(orientations(&shape).len(), 4);
+    }
+
+    #[test]
+    fn basic_test_part1() {
+        assert_eq!(part1(BASIC_INPUT, 6), 2);
+    }
+
+    /*
+    #[test]
+    fn basic_test_part2() {
+        assert_eq!(part2(BASIC_INPUT_P2), 2);
+    }
+    */
+}